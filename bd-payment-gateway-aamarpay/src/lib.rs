@@ -1,15 +1,21 @@
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bd_payment_gateway_core::{
-    BdPaymentError, Currency, Environment, HttpClient, HttpSettings, InitiatePaymentResponse,
-    PaymentProvider, PaymentStatus, RefundResponse, Result, VerifyPaymentResponse,
+    http::add_default_headers, BdPaymentError, Currency, Environment, HttpClient, HttpSettings,
+    InMemoryIdempotencyStore, InitiatePaymentResponse, PaymentProvider, PaymentStatus,
+    RefundResponse, RefundStatus, Result, VerifyPaymentResponse,
 };
+use rand::Rng;
 use reqwest::Method;
 use rust_decimal::Decimal;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::time::sleep;
 use url::Url;
 
 const SANDBOX_BASE: &str = "https://sandbox.aamarpay.com";
@@ -21,6 +27,11 @@ pub struct Config {
     pub signature_key: SecretString,
     pub environment: Environment,
     pub http_settings: HttpSettings,
+    /// aamarPay does not publish a refund endpoint for every merchant account. Refunds are
+    /// only attempted when this is set to the endpoint your aamarPay representative has
+    /// provisioned for you; otherwise [`AamarpayClient::refund`] returns
+    /// [`BdPaymentError::unsupported`].
+    pub refund_endpoint: Option<Url>,
 }
 
 impl Config {
@@ -54,7 +65,11 @@ impl AamarpayClient {
     pub fn new(config: Config) -> Result<Self> {
         config.validate()?;
         let base_url = config.environment.resolve(SANDBOX_BASE, PRODUCTION_BASE)?;
-        let http = HttpClient::new(config.http_settings.clone(), None)?;
+        // Attaches a process-local idempotency store so that `idempotency-key`-bearing requests
+        // (e.g. `refund`) are actually deduplicated, rather than just carrying a header nobody
+        // consults.
+        let http = HttpClient::new(config.http_settings.clone(), None)?
+            .with_idempotency_store(Arc::new(InMemoryIdempotencyStore::new()));
 
         Ok(Self {
             config,
@@ -62,6 +77,162 @@ impl AamarpayClient {
             base_url,
         })
     }
+
+    /// Processes a server-to-server aamarPay IPN/callback POST.
+    ///
+    /// Callback parameters are spoofable, so a reported `pay_status` of `Paid` is never
+    /// trusted directly: instead this re-fetches the transaction from
+    /// `/api/v1/trxcheck/request.php` and returns that authoritative result.
+    pub async fn verify_callback(
+        &self,
+        raw_params: &HashMap<String, String>,
+    ) -> Result<VerifyPaymentResponse> {
+        let callback = parse_callback(raw_params)?;
+
+        if callback.store_id != self.config.store_id {
+            return Err(BdPaymentError::validation(
+                "aamarPay callback store_id does not match the configured store_id.",
+                "Reject this callback; it may be misrouted or forged.",
+            ));
+        }
+
+        let request_id = callback
+            .request_id
+            .clone()
+            .or_else(|| callback.mer_txnid.clone())
+            .unwrap_or_else(|| callback.tran_id.clone());
+
+        let reported_status = map_status(&callback.pay_status);
+
+        if matches!(reported_status, PaymentStatus::Paid) {
+            return self
+                .verify_payment(&VerifyPaymentRequest { request_id })
+                .await;
+        }
+
+        let amount = Decimal::from_str(&callback.amount).ok();
+        let currency = Some(parse_currency(&callback.currency));
+        let raw = serde_json::to_value(&callback).unwrap_or(Value::Null);
+
+        let failure_reason = failure_reason_for(&reported_status, &raw);
+
+        Ok(VerifyPaymentResponse {
+            status: reported_status,
+            provider_reference: request_id,
+            amount,
+            currency: currency.clone(),
+            money: amount
+                .zip(currency)
+                .map(|(amount, currency)| bd_payment_gateway_core::Money { amount, currency }),
+            raw,
+            request_id: None,
+            failure_reason,
+            network_transaction_id: None,
+        })
+    }
+
+    /// Repeatedly calls [`Self::verify_payment`] until it reaches a terminal [`PaymentStatus`]
+    /// (`Paid`/`Failed`/`Cancelled`) or `policy.max_attempts` is exhausted, sleeping with
+    /// jittered exponential backoff between attempts so callers don't have to hand-roll a
+    /// settlement-polling loop around a transaction stuck in `Pending`.
+    pub async fn poll_until_settled(
+        &self,
+        req: &VerifyPaymentRequest,
+        policy: PollPolicy,
+    ) -> Result<VerifyPaymentResponse> {
+        let mut delay = policy.initial_delay;
+        let mut last = self.verify_payment(req).await?;
+
+        for _ in 1..policy.max_attempts.max(1) {
+            if is_terminal(&last.status) {
+                return Ok(last);
+            }
+
+            sleep(jittered(delay)).await;
+            delay = Duration::from_secs_f64(delay.as_secs_f64() * policy.multiplier).min(policy.max_delay);
+
+            last = self.verify_payment(req).await?;
+        }
+
+        if is_terminal(&last.status) {
+            Ok(last)
+        } else {
+            Err(BdPaymentError::timeout(
+                format!(
+                    "aamarPay transaction did not settle after {} attempts; last status: {:?}",
+                    policy.max_attempts, last.status
+                ),
+                "Increase PollPolicy.max_attempts, raise max_delay, or poll again later.",
+                policy.max_attempts,
+            ))
+        }
+    }
+}
+
+/// Controls [`AamarpayClient::poll_until_settled`]'s attempt budget and jittered
+/// exponential backoff schedule.
+#[derive(Debug, Clone)]
+pub struct PollPolicy {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+}
+
+impl Default for PollPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+        }
+    }
+}
+
+fn is_terminal(status: &PaymentStatus) -> bool {
+    matches!(
+        status,
+        PaymentStatus::Paid | PaymentStatus::Failed | PaymentStatus::Cancelled
+    )
+}
+
+fn jittered(base: Duration) -> Duration {
+    let jitter_factor = rand::thread_rng().gen_range(0.5..=1.0);
+    Duration::from_secs_f64(base.as_secs_f64() * jitter_factor)
+}
+
+/// Form-encoded fields aamarPay POSTs to `success_url`/`fail_url`/`cancel_url` after checkout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Callback {
+    pub store_id: String,
+    pub tran_id: String,
+    pub amount: String,
+    pub currency: String,
+    pub pay_status: String,
+    pub card_type: Option<String>,
+    pub request_id: Option<String>,
+    pub mer_txnid: Option<String>,
+    pub opt_a: Option<String>,
+    pub opt_b: Option<String>,
+    pub opt_c: Option<String>,
+    pub opt_d: Option<String>,
+}
+
+fn parse_callback(raw_params: &HashMap<String, String>) -> Result<Callback> {
+    let value = serde_json::to_value(raw_params).map_err(|e| {
+        BdPaymentError::parse(
+            format!("Failed to encode aamarPay callback params: {e}"),
+            "Ensure callback params are simple string key/value pairs.",
+        )
+    })?;
+
+    serde_json::from_value(value).map_err(|e| {
+        BdPaymentError::parse(
+            format!("Failed to parse aamarPay callback payload: {e}"),
+            "Confirm aamarPay posted the documented IPN fields (store_id, tran_id, amount, currency, pay_status).",
+        )
+    })
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -111,6 +282,231 @@ impl InitiatePaymentRequest {
         }
         Ok(())
     }
+
+    pub fn builder() -> InitiatePaymentRequestBuilder {
+        InitiatePaymentRequestBuilder::default()
+    }
+}
+
+/// Fluent builder for [`InitiatePaymentRequest`]. `build()` runs the same validation as
+/// [`InitiatePaymentRequest::validate`], so a successfully built request is always send-ready.
+#[derive(Debug, Clone, Default)]
+pub struct InitiatePaymentRequestBuilder {
+    tran_id: Option<String>,
+    amount: Option<String>,
+    currency: Option<String>,
+    success_url: Option<Url>,
+    fail_url: Option<Url>,
+    cancel_url: Option<Url>,
+    desc: Option<String>,
+    cus_name: Option<String>,
+    cus_email: Option<String>,
+    cus_add1: Option<String>,
+    cus_add2: Option<String>,
+    cus_city: Option<String>,
+    cus_state: Option<String>,
+    cus_postcode: Option<String>,
+    cus_country: Option<String>,
+    cus_phone: Option<String>,
+    opt_a: Option<String>,
+    opt_b: Option<String>,
+    opt_c: Option<String>,
+    opt_d: Option<String>,
+    signature_key: Option<SecretString>,
+}
+
+impl InitiatePaymentRequestBuilder {
+    pub fn tran_id(mut self, v: impl Into<String>) -> Self {
+        self.tran_id = Some(v.into());
+        self
+    }
+
+    pub fn amount(mut self, v: impl Into<String>) -> Self {
+        self.amount = Some(v.into());
+        self
+    }
+
+    pub fn currency(mut self, v: impl Into<String>) -> Self {
+        self.currency = Some(v.into());
+        self
+    }
+
+    pub fn success_url(mut self, v: Url) -> Self {
+        self.success_url = Some(v);
+        self
+    }
+
+    pub fn fail_url(mut self, v: Url) -> Self {
+        self.fail_url = Some(v);
+        self
+    }
+
+    pub fn cancel_url(mut self, v: Url) -> Self {
+        self.cancel_url = Some(v);
+        self
+    }
+
+    pub fn desc(mut self, v: impl Into<String>) -> Self {
+        self.desc = Some(v.into());
+        self
+    }
+
+    pub fn cus_name(mut self, v: impl Into<String>) -> Self {
+        self.cus_name = Some(v.into());
+        self
+    }
+
+    pub fn cus_email(mut self, v: impl Into<String>) -> Self {
+        self.cus_email = Some(v.into());
+        self
+    }
+
+    pub fn cus_add1(mut self, v: impl Into<String>) -> Self {
+        self.cus_add1 = Some(v.into());
+        self
+    }
+
+    pub fn cus_add2(mut self, v: impl Into<String>) -> Self {
+        self.cus_add2 = Some(v.into());
+        self
+    }
+
+    pub fn cus_city(mut self, v: impl Into<String>) -> Self {
+        self.cus_city = Some(v.into());
+        self
+    }
+
+    pub fn cus_state(mut self, v: impl Into<String>) -> Self {
+        self.cus_state = Some(v.into());
+        self
+    }
+
+    pub fn cus_postcode(mut self, v: impl Into<String>) -> Self {
+        self.cus_postcode = Some(v.into());
+        self
+    }
+
+    pub fn cus_country(mut self, v: impl Into<String>) -> Self {
+        self.cus_country = Some(v.into());
+        self
+    }
+
+    pub fn cus_phone(mut self, v: impl Into<String>) -> Self {
+        self.cus_phone = Some(v.into());
+        self
+    }
+
+    pub fn opt_a(mut self, v: impl Into<String>) -> Self {
+        self.opt_a = Some(v.into());
+        self
+    }
+
+    pub fn opt_b(mut self, v: impl Into<String>) -> Self {
+        self.opt_b = Some(v.into());
+        self
+    }
+
+    pub fn opt_c(mut self, v: impl Into<String>) -> Self {
+        self.opt_c = Some(v.into());
+        self
+    }
+
+    pub fn opt_d(mut self, v: impl Into<String>) -> Self {
+        self.opt_d = Some(v.into());
+        self
+    }
+
+    pub fn signature_key(mut self, v: SecretString) -> Self {
+        self.signature_key = Some(v);
+        self
+    }
+
+    fn require(field: Option<String>, message: &str, hint: &str) -> Result<String> {
+        field
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| BdPaymentError::validation(message.to_owned(), hint.to_owned()))
+    }
+
+    fn require_url(field: Option<Url>, message: &str, hint: &str) -> Result<Url> {
+        field.ok_or_else(|| BdPaymentError::validation(message.to_owned(), hint.to_owned()))
+    }
+
+    pub fn build(self) -> Result<InitiatePaymentRequest> {
+        let req = InitiatePaymentRequest {
+            tran_id: Self::require(
+                self.tran_id,
+                "tran_id is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .tran_id(..) before .build().",
+            )?,
+            amount: Self::require(
+                self.amount,
+                "amount is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .amount(..) before .build().",
+            )?,
+            currency: Self::require(
+                self.currency,
+                "currency is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .currency(..) before .build().",
+            )?,
+            success_url: Self::require_url(
+                self.success_url,
+                "success_url is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .success_url(..) before .build().",
+            )?,
+            fail_url: Self::require_url(
+                self.fail_url,
+                "fail_url is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .fail_url(..) before .build().",
+            )?,
+            cancel_url: Self::require_url(
+                self.cancel_url,
+                "cancel_url is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .cancel_url(..) before .build().",
+            )?,
+            desc: self.desc,
+            cus_name: Self::require(
+                self.cus_name,
+                "cus_name is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .cus_name(..) before .build().",
+            )?,
+            cus_email: Self::require(
+                self.cus_email,
+                "cus_email is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .cus_email(..) before .build().",
+            )?,
+            cus_add1: Self::require(
+                self.cus_add1,
+                "cus_add1 is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .cus_add1(..) before .build().",
+            )?,
+            cus_add2: self.cus_add2,
+            cus_city: Self::require(
+                self.cus_city,
+                "cus_city is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .cus_city(..) before .build().",
+            )?,
+            cus_state: self.cus_state,
+            cus_postcode: self.cus_postcode,
+            cus_country: Self::require(
+                self.cus_country,
+                "cus_country is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .cus_country(..) before .build().",
+            )?,
+            cus_phone: Self::require(
+                self.cus_phone,
+                "cus_phone is required to build an aamarPay InitiatePaymentRequest.",
+                "Call .cus_phone(..) before .build().",
+            )?,
+            opt_a: self.opt_a,
+            opt_b: self.opt_b,
+            opt_c: self.opt_c,
+            opt_d: self.opt_d,
+            signature_key: self.signature_key,
+        };
+
+        req.validate()?;
+        Ok(req)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +514,31 @@ pub struct VerifyPaymentRequest {
     pub request_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundRequest {
+    pub tran_id: String,
+    pub request_id: String,
+    pub amount: Option<String>,
+    pub reason: Option<String>,
+    /// Caller-supplied idempotency key used to deduplicate a retried refund request against
+    /// the client's local idempotency store (aamarPay's API has no idempotency field of its
+    /// own). Defaults to a key derived from `tran_id`/`request_id` when absent, so retries of
+    /// the same transaction still dedupe even if the caller omits this.
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RefundPostRequest<'a> {
+    store_id: &'a str,
+    signature_key: &'a str,
+    tran_id: &'a str,
+    request_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+}
+
 #[derive(Debug, Serialize)]
 struct JsonPostRequest<'a> {
     store_id: &'a str,
@@ -156,7 +577,10 @@ struct JsonPostRequest<'a> {
 impl PaymentProvider for AamarpayClient {
     type InitiateRequest = InitiatePaymentRequest;
     type VerifyRequest = VerifyPaymentRequest;
-    type RefundRequest = Value;
+    type RefundRequest = RefundRequest;
+    type AuthorizeRequest = ();
+    type CaptureRequest = ();
+    type VoidRequest = ();
 
     async fn initiate_payment(
         &self,
@@ -255,6 +679,7 @@ impl PaymentProvider for AamarpayClient {
             provider_reference,
             raw,
             request_id: None,
+            network_transaction_id: None,
         })
     }
 
@@ -287,20 +712,8 @@ impl PaymentProvider for AamarpayClient {
             .get("pay_status")
             .or_else(|| raw.get("status"))
             .and_then(Value::as_str)
-            .unwrap_or("unknown")
-            .to_ascii_lowercase();
-
-        let payment_status = if status.contains("successful") || status.contains("paid") {
-            PaymentStatus::Paid
-        } else if status.contains("pending") {
-            PaymentStatus::Pending
-        } else if status.contains("cancel") {
-            PaymentStatus::Cancelled
-        } else if status.contains("fail") {
-            PaymentStatus::Failed
-        } else {
-            PaymentStatus::Unknown(status)
-        };
+            .unwrap_or("unknown");
+        let payment_status = map_status(status);
 
         let amount = raw
             .get("amount")
@@ -311,6 +724,8 @@ impl PaymentProvider for AamarpayClient {
             .and_then(Value::as_str)
             .map(parse_currency);
 
+        let failure_reason = failure_reason_for(&payment_status, &raw);
+
         Ok(VerifyPaymentResponse {
             status: payment_status,
             provider_reference: req.request_id.clone(),
@@ -321,14 +736,96 @@ impl PaymentProvider for AamarpayClient {
                 .map(|(amount, currency)| bd_payment_gateway_core::Money { amount, currency }),
             raw,
             request_id: None,
+            failure_reason,
+            network_transaction_id: None,
         })
     }
 
-    async fn refund(&self, _req: &Self::RefundRequest) -> Result<RefundResponse> {
-        Err(BdPaymentError::unsupported(
-            "aamarPay refund endpoint is not published in this SDK scope.",
-            "Use aamarPay merchant dashboard or add provider-specific refund API when officially documented.",
-        ))
+    async fn refund(&self, req: &Self::RefundRequest) -> Result<RefundResponse> {
+        let endpoint = self.config.refund_endpoint.as_ref().ok_or_else(|| {
+            BdPaymentError::unsupported(
+                "aamarPay refund endpoint is not configured for this merchant account.",
+                "Set Config.refund_endpoint once your aamarPay representative provisions a refund URL, or use the merchant dashboard instead.",
+            )
+        })?;
+
+        if req.tran_id.trim().is_empty() || req.request_id.trim().is_empty() {
+            return Err(BdPaymentError::validation(
+                "tran_id and request_id are required for aamarPay refund requests.",
+                "Pass the tran_id and request_id returned from initiate_payment.",
+            ));
+        }
+
+        // aamarPay's refund API has no idempotency-key field of its own, so double-refund
+        // protection lives entirely in the locally-attached IdempotencyStore keyed off this
+        // header. Falling back to `generate_idempotency_key` here would mint a fresh random key
+        // on every call, defeating dedup for the common case where a caller retries a refund
+        // without remembering to pass back the same key — so the fallback is instead derived
+        // deterministically from tran_id/request_id, the fields that already identify a unique
+        // refund attempt.
+        let idempotency_key = req
+            .idempotency_key
+            .clone()
+            .unwrap_or_else(|| format!("aamarpay-refund-{}-{}", req.tran_id, req.request_id));
+        let headers = add_default_headers(
+            reqwest::header::HeaderMap::new(),
+            None,
+            Some(&idempotency_key),
+        )?;
+
+        let body = RefundPostRequest {
+            store_id: &self.config.store_id,
+            signature_key: self.config.signature_key.expose_secret(),
+            tran_id: &req.tran_id,
+            request_id: &req.request_id,
+            amount: req.amount.as_deref(),
+            reason: req.reason.as_deref(),
+        };
+
+        let raw: Value = self
+            .http
+            .request_json(Method::POST, endpoint, headers, Some(&body))
+            .await?;
+
+        if is_failure(&raw) {
+            return Err(BdPaymentError::provider(
+                raw.get("msg")
+                    .or_else(|| raw.get("message"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("aamarPay rejected refund request."),
+                "Verify tran_id/request_id and that the transaction is eligible for refund.",
+                raw.get("status_code")
+                    .and_then(Value::as_i64)
+                    .map(|v| v.to_string()),
+                None,
+            ));
+        }
+
+        let status = raw
+            .get("status")
+            .or_else(|| raw.get("refund_status"))
+            .and_then(Value::as_str)
+            .map(|status| match status.to_ascii_lowercase().as_str() {
+                s if s.contains("complete") || s.contains("success") => RefundStatus::Completed,
+                s if s.contains("pending") => RefundStatus::Pending,
+                s if s.contains("fail") => RefundStatus::Failed,
+                other => RefundStatus::Unknown(other.to_owned()),
+            })
+            .unwrap_or(RefundStatus::Pending);
+
+        let provider_reference = raw
+            .get("refund_ref_id")
+            .or_else(|| raw.get("request_id"))
+            .and_then(Value::as_str)
+            .unwrap_or(&req.request_id)
+            .to_owned();
+
+        Ok(RefundResponse {
+            status,
+            provider_reference,
+            raw,
+            request_id: Some(req.request_id.clone()),
+        })
     }
 }
 
@@ -337,10 +834,36 @@ fn parse_currency(raw: &str) -> Currency {
         "BDT" => Currency::Bdt,
         "USD" => Currency::Usd,
         "EUR" => Currency::Eur,
-        other => Currency::Other(other.to_owned()),
+        other => Currency::other(other.to_owned()),
     }
 }
 
+fn map_status(status: &str) -> PaymentStatus {
+    let status = status.to_ascii_lowercase();
+    if status.contains("successful") || status.contains("paid") {
+        PaymentStatus::Paid
+    } else if status.contains("pending") {
+        PaymentStatus::Pending
+    } else if status.contains("cancel") {
+        PaymentStatus::Cancelled
+    } else if status.contains("fail") {
+        PaymentStatus::Failed
+    } else {
+        PaymentStatus::Unknown(status)
+    }
+}
+
+/// Extracts a decline reason out of whatever reason/message field the callback or transaction
+/// check response happens to carry, for the subset of calls where the status came back `Failed`.
+fn failure_reason_for(
+    status: &PaymentStatus,
+    raw: &Value,
+) -> Option<bd_payment_gateway_core::FailureReason> {
+    matches!(status, PaymentStatus::Failed).then(|| {
+        bd_payment_gateway_core::extract_failure_reason(raw, "aamarPay reported a failed payment.")
+    })
+}
+
 fn is_failure(raw: &Value) -> bool {
     raw.get("result")
         .and_then(Value::as_bool)
@@ -353,12 +876,164 @@ fn is_failure(raw: &Value) -> bool {
         .unwrap_or(false)
 }
 
+/// Minimal JSON shape accepted by this provider's [`bd_payment_gateway_core::registry`]
+/// registration. Deliberately narrower than [`Config`]: it covers the fields every merchant must
+/// supply and leaves `http_settings`/`refund_endpoint` at their defaults, since the registry's
+/// `build` hook has no channel for per-caller tuning knobs.
+#[derive(Deserialize)]
+struct RegistryConfig {
+    store_id: String,
+    signature_key: String,
+    environment: Environment,
+}
+
+fn registry_build(
+    config: bd_payment_gateway_core::registry::ProviderConfigJson,
+) -> Result<Box<dyn bd_payment_gateway_core::DynPaymentProvider>> {
+    let cfg: RegistryConfig = serde_json::from_value(config).map_err(|e| {
+        BdPaymentError::config(
+            format!("Invalid aamarPay registry config: {e}"),
+            "Provide store_id, signature_key, and environment.",
+        )
+    })?;
+    let client = AamarpayClient::new(Config {
+        store_id: cfg.store_id,
+        signature_key: SecretString::new(cfg.signature_key.into()),
+        environment: cfg.environment,
+        http_settings: HttpSettings::default(),
+        refund_endpoint: None,
+    })?;
+    Ok(Box::new(client))
+}
+
+/// aamarPay does not document a callback signature, so this only normalizes the posted
+/// `pay_status`, it does not authenticate it; callers still need `verify_callback` (which
+/// re-fetches `/api/v1/trxcheck/request.php`) to confirm settlement before acting on the
+/// callback.
+fn registry_parse_webhook(
+    payload: &Value,
+) -> Result<bd_payment_gateway_core::registry::NormalizedEvent> {
+    let callback: Callback = serde_json::from_value(payload.clone()).map_err(|e| {
+        BdPaymentError::parse(
+            format!("Failed to parse aamarPay callback payload: {e}"),
+            "Confirm aamarPay posted the documented IPN fields (store_id, tran_id, amount, currency, pay_status).",
+        )
+    })?;
+    let provider_reference = callback
+        .mer_txnid
+        .clone()
+        .unwrap_or_else(|| callback.tran_id.clone());
+
+    Ok(bd_payment_gateway_core::registry::NormalizedEvent {
+        provider: "aamarpay",
+        status: map_status(&callback.pay_status),
+        provider_reference,
+        raw: payload.clone(),
+    })
+}
+
+inventory::submit! {
+    bd_payment_gateway_core::registry::ProviderRegistration {
+        name: "aamarpay",
+        build: registry_build,
+        parse_webhook: registry_parse_webhook,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use httpmock::Method::POST;
     use httpmock::MockServer;
 
+    #[test]
+    fn builder_builds_valid_request_with_defaulted_optionals() {
+        let req = InitiatePaymentRequest::builder()
+            .tran_id("T-1")
+            .amount("120.00")
+            .currency("BDT")
+            .success_url(Url::parse("https://merchant.test/s").expect("url"))
+            .fail_url(Url::parse("https://merchant.test/f").expect("url"))
+            .cancel_url(Url::parse("https://merchant.test/c").expect("url"))
+            .cus_name("Demo")
+            .cus_email("demo@example.com")
+            .cus_add1("Dhaka")
+            .cus_city("Dhaka")
+            .cus_country("Bangladesh")
+            .cus_phone("017")
+            .build()
+            .expect("builder should produce a valid request");
+
+        assert_eq!(req.tran_id, "T-1");
+        assert!(req.desc.is_none());
+        assert!(req.opt_a.is_none());
+    }
+
+    #[test]
+    fn builder_rejects_missing_required_field() {
+        let err = InitiatePaymentRequest::builder()
+            .amount("120.00")
+            .currency("BDT")
+            .build()
+            .expect_err("tran_id is required");
+
+        assert!(matches!(err, BdPaymentError::ValidationError { .. }));
+    }
+
+    #[tokio::test]
+    async fn verify_callback_rejects_mismatched_store_id() {
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::Sandbox,
+            http_settings: HttpSettings::default(),
+            refund_endpoint: None,
+        })
+        .expect("client");
+
+        let mut params = HashMap::new();
+        params.insert("store_id".to_owned(), "other-store".to_owned());
+        params.insert("tran_id".to_owned(), "T-1".to_owned());
+        params.insert("amount".to_owned(), "100.00".to_owned());
+        params.insert("currency".to_owned(), "BDT".to_owned());
+        params.insert("pay_status".to_owned(), "Successful".to_owned());
+
+        let err = client
+            .verify_callback(&params)
+            .await
+            .expect_err("mismatched store_id should be rejected");
+
+        assert!(matches!(err, BdPaymentError::ValidationError { .. }));
+    }
+
+    #[tokio::test]
+    async fn verify_callback_trusts_non_paid_status_without_reconfirming() {
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::Sandbox,
+            http_settings: HttpSettings::default(),
+            refund_endpoint: None,
+        })
+        .expect("client");
+
+        let mut params = HashMap::new();
+        params.insert("store_id".to_owned(), "store".to_owned());
+        params.insert("tran_id".to_owned(), "T-1".to_owned());
+        params.insert("request_id".to_owned(), "REQ-1".to_owned());
+        params.insert("amount".to_owned(), "100.00".to_owned());
+        params.insert("currency".to_owned(), "BDT".to_owned());
+        params.insert("pay_status".to_owned(), "Cancel".to_owned());
+
+        let result = client
+            .verify_callback(&params)
+            .await
+            .expect("cancelled callback should not require re-confirmation");
+
+        assert!(matches!(result.status, PaymentStatus::Cancelled));
+        assert_eq!(result.provider_reference, "REQ-1");
+    }
+
     #[test]
     fn request_validation_requires_numeric_amount() {
         let req = InitiatePaymentRequest {
@@ -407,6 +1082,7 @@ mod tests {
                 Url::parse(&server.base_url()).expect("mock server url"),
             ),
             http_settings: HttpSettings::default(),
+            refund_endpoint: None,
         })
         .expect("client");
 
@@ -443,4 +1119,237 @@ mod tests {
             "https://sandbox.aamarpay.com/pay/abc"
         );
     }
+
+    #[tokio::test]
+    async fn refund_without_configured_endpoint_is_unsupported() {
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::Sandbox,
+            http_settings: HttpSettings::default(),
+            refund_endpoint: None,
+        })
+        .expect("client");
+
+        let err = PaymentProvider::refund(
+            &client,
+            &RefundRequest {
+                tran_id: "T-1".to_owned(),
+                request_id: "REQ-1".to_owned(),
+                amount: None,
+                reason: None,
+                idempotency_key: None,
+            },
+        )
+        .await
+        .expect_err("refund without a configured endpoint should be unsupported");
+
+        assert!(matches!(err, BdPaymentError::Unsupported { .. }));
+    }
+
+    #[tokio::test]
+    async fn refund_echoes_idempotency_key_and_parses_response() {
+        let server = MockServer::start();
+        let refund_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/refund.php")
+                .header_exists("idempotency-key");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "result": true,
+                "status": "Completed",
+                "refund_ref_id": "RFD-1"
+            }));
+        });
+
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::Sandbox,
+            http_settings: HttpSettings::default(),
+            refund_endpoint: Some(
+                Url::parse(&format!("{}/refund.php", server.base_url())).expect("valid url"),
+            ),
+        })
+        .expect("client");
+
+        let result = PaymentProvider::refund(
+            &client,
+            &RefundRequest {
+                tran_id: "T-1".to_owned(),
+                request_id: "REQ-1".to_owned(),
+                amount: Some("50.00".to_owned()),
+                reason: Some("customer request".to_owned()),
+                idempotency_key: Some("idem-key-1".to_owned()),
+            },
+        )
+        .await
+        .expect("refund");
+
+        refund_mock.assert();
+        assert!(matches!(result.status, RefundStatus::Completed));
+        assert_eq!(result.provider_reference, "RFD-1");
+    }
+
+    #[tokio::test]
+    async fn refund_retried_with_same_idempotency_key_hits_provider_once() {
+        let server = MockServer::start();
+        let refund_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/refund.php")
+                .header("idempotency-key", "idem-key-retry");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "result": true,
+                "status": "Completed",
+                "refund_ref_id": "RFD-1"
+            }));
+        });
+
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::Sandbox,
+            http_settings: HttpSettings::default(),
+            refund_endpoint: Some(
+                Url::parse(&format!("{}/refund.php", server.base_url())).expect("valid url"),
+            ),
+        })
+        .expect("client");
+
+        let req = RefundRequest {
+            tran_id: "T-1".to_owned(),
+            request_id: "REQ-1".to_owned(),
+            amount: Some("50.00".to_owned()),
+            reason: Some("customer request".to_owned()),
+            idempotency_key: Some("idem-key-retry".to_owned()),
+        };
+
+        let first = PaymentProvider::refund(&client, &req)
+            .await
+            .expect("first refund attempt");
+        let retried = PaymentProvider::refund(&client, &req)
+            .await
+            .expect("retried refund attempt should replay the cached result");
+
+        refund_mock.assert_hits(1);
+        assert_eq!(first.provider_reference, retried.provider_reference);
+    }
+
+    #[tokio::test]
+    async fn refund_retried_without_explicit_key_still_deduplicates_by_transaction() {
+        let server = MockServer::start();
+        let refund_mock = server.mock(|when, then| {
+            when.method(POST).path("/refund.php");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "result": true,
+                "status": "Completed",
+                "refund_ref_id": "RFD-1"
+            }));
+        });
+
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::Sandbox,
+            http_settings: HttpSettings::default(),
+            refund_endpoint: Some(
+                Url::parse(&format!("{}/refund.php", server.base_url())).expect("valid url"),
+            ),
+        })
+        .expect("client");
+
+        let req = RefundRequest {
+            tran_id: "T-1".to_owned(),
+            request_id: "REQ-1".to_owned(),
+            amount: Some("50.00".to_owned()),
+            reason: Some("customer request".to_owned()),
+            idempotency_key: None,
+        };
+
+        PaymentProvider::refund(&client, &req)
+            .await
+            .expect("first refund attempt");
+        PaymentProvider::refund(&client, &req)
+            .await
+            .expect("retried refund attempt should not re-hit the provider");
+
+        refund_mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn poll_until_settled_returns_immediately_on_terminal_status() {
+        let server = MockServer::start();
+        let verify_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/api/v1/trxcheck/request.php");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "pay_status": "Successful",
+                "amount": "100.00",
+                "currency": "BDT"
+            }));
+        });
+
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse(&server.base_url()).expect("mock server url"),
+            ),
+            http_settings: HttpSettings::default(),
+            refund_endpoint: None,
+        })
+        .expect("client");
+
+        let result = client
+            .poll_until_settled(
+                &VerifyPaymentRequest {
+                    request_id: "REQ-1".to_owned(),
+                },
+                PollPolicy::default(),
+            )
+            .await
+            .expect("poll");
+
+        verify_mock.assert_hits(1);
+        assert!(matches!(result.status, PaymentStatus::Paid));
+    }
+
+    #[tokio::test]
+    async fn poll_until_settled_times_out_while_pending() {
+        let server = MockServer::start();
+        let _verify_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/api/v1/trxcheck/request.php");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "pay_status": "Pending"
+            }));
+        });
+
+        let client = AamarpayClient::new(Config {
+            store_id: "store".to_owned(),
+            signature_key: SecretString::new("secret".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse(&server.base_url()).expect("mock server url"),
+            ),
+            http_settings: HttpSettings::default(),
+            refund_endpoint: None,
+        })
+        .expect("client");
+
+        let err = client
+            .poll_until_settled(
+                &VerifyPaymentRequest {
+                    request_id: "REQ-1".to_owned(),
+                },
+                PollPolicy {
+                    max_attempts: 2,
+                    initial_delay: Duration::from_millis(1),
+                    max_delay: Duration::from_millis(2),
+                    multiplier: 2.0,
+                },
+            )
+            .await
+            .expect_err("pending status should exhaust the attempt budget");
+
+        assert!(matches!(err, BdPaymentError::TimeoutError { .. }));
+    }
 }
@@ -1,4 +1,9 @@
+use std::time::Duration;
+
 use thiserror::Error;
+use url::Url;
+
+use crate::http::{redact_text, RedactionPolicy};
 
 pub type Result<T> = std::result::Result<T, BdPaymentError>;
 
@@ -10,6 +15,10 @@ pub enum ErrorCode {
     ProviderRejected,
     UnsupportedOperation,
     ParseFailed,
+    PollTimeout,
+    PluginFailure,
+    RequestTimeout,
+    RateLimited,
 }
 
 impl ErrorCode {
@@ -21,6 +30,10 @@ impl ErrorCode {
             Self::ProviderRejected => "PROVIDER_REJECTED",
             Self::UnsupportedOperation => "UNSUPPORTED_OPERATION",
             Self::ParseFailed => "PARSE_FAILED",
+            Self::PollTimeout => "POLL_TIMEOUT",
+            Self::PluginFailure => "PLUGIN_FAILURE",
+            Self::RequestTimeout => "REQUEST_TIMEOUT",
+            Self::RateLimited => "RATE_LIMITED",
         }
     }
 }
@@ -31,6 +44,27 @@ impl std::fmt::Display for ErrorCode {
     }
 }
 
+/// One field-level complaint within a provider's error payload (PayPal's `details[]`, for
+/// example), so callers can show per-field validation messages instead of a single blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderErrorDetail {
+    pub field: Option<String>,
+    pub issue: String,
+    pub description: Option<String>,
+}
+
+/// A coarse classification of why a provider rejected a request, for callers that want to branch
+/// on the failure category (e.g. retry on `RateLimit`, surface `Authentication` as a config
+/// problem) without parsing `provider_code`/`details` themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProviderErrorType {
+    Authentication,
+    InvalidRequest,
+    RateLimit,
+    ProviderUnavailable,
+    Unknown,
+}
+
 #[derive(Debug, Error)]
 pub enum BdPaymentError {
     #[error("Configuration error: {message}. Hint: {hint}")]
@@ -53,6 +87,11 @@ pub enum BdPaymentError {
         status: Option<u16>,
         request_id: Option<String>,
         body: Option<String>,
+        /// How many times [`HttpClient`](crate::http::HttpClient) sent this request (1 means it
+        /// failed on the first try, with no retries attempted) before giving up or hitting a
+        /// non-retryable response, so callers can tell a transient blip from a persistently
+        /// failing endpoint when logging session-failure patterns.
+        attempt: u32,
     },
     #[error("Provider rejected request: {message}. Hint: {hint}")]
     ProviderError {
@@ -61,6 +100,10 @@ pub enum BdPaymentError {
         hint: String,
         provider_code: Option<String>,
         request_id: Option<String>,
+        debug_id: Option<String>,
+        details: Vec<ProviderErrorDetail>,
+        help_links: Vec<Url>,
+        error_type: Option<ProviderErrorType>,
     },
     #[error("Operation unsupported: {message}. Hint: {hint}")]
     Unsupported {
@@ -74,6 +117,40 @@ pub enum BdPaymentError {
         message: String,
         hint: String,
     },
+    #[error("Polling timed out after {attempts} attempts: {message}. Hint: {hint}")]
+    TimeoutError {
+        code: ErrorCode,
+        message: String,
+        hint: String,
+        attempts: u32,
+    },
+    #[error("WASM plugin {module:?} operation {operation:?} failed: {message}. Hint: {hint}")]
+    PluginError {
+        code: ErrorCode,
+        message: String,
+        hint: String,
+        module: String,
+        operation: String,
+    },
+    #[error("Request timed out: {message}. Hint: {hint}")]
+    Timeout {
+        code: ErrorCode,
+        message: String,
+        hint: String,
+    },
+    #[error("Rate limited by provider: {message}. Hint: {hint}")]
+    RateLimited {
+        code: ErrorCode,
+        message: String,
+        hint: String,
+        retry_after: Option<Duration>,
+    },
+    #[error("HTTP transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("JSON (de)serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid URL: {0}")]
+    UrlParse(#[from] url::ParseError),
 }
 
 impl BdPaymentError {
@@ -84,7 +161,14 @@ impl BdPaymentError {
             | Self::HttpError { code, .. }
             | Self::ProviderError { code, .. }
             | Self::Unsupported { code, .. }
-            | Self::ParseError { code, .. } => *code,
+            | Self::ParseError { code, .. }
+            | Self::TimeoutError { code, .. }
+            | Self::PluginError { code, .. }
+            | Self::Timeout { code, .. }
+            | Self::RateLimited { code, .. } => *code,
+            Self::Transport(_) => ErrorCode::HttpFailure,
+            Self::Json(_) => ErrorCode::ParseFailed,
+            Self::UrlParse(_) => ErrorCode::ValidationFailed,
         }
     }
 
@@ -95,7 +179,153 @@ impl BdPaymentError {
             | Self::HttpError { hint, .. }
             | Self::ProviderError { hint, .. }
             | Self::Unsupported { hint, .. }
-            | Self::ParseError { hint, .. } => hint,
+            | Self::ParseError { hint, .. }
+            | Self::TimeoutError { hint, .. }
+            | Self::PluginError { hint, .. }
+            | Self::Timeout { hint, .. }
+            | Self::RateLimited { hint, .. } => hint,
+            Self::Transport(_) => "Check DNS, connectivity, TLS trust roots, and provider uptime.",
+            Self::Json(_) => "Ensure the JSON being parsed/serialized matches the expected schema.",
+            Self::UrlParse(_) => {
+                "Check that the URL was built correctly (e.g. provider base URL plus path)."
+            }
+        }
+    }
+
+    /// Whether an SDK-level retry loop should attempt this call again. `Timeout` and
+    /// `RateLimited` are always retryable; an `HttpError` is retryable only for the same
+    /// statuses [`HttpClient`](crate::http::HttpClient) itself retries (429 and 5xx); a
+    /// `Transport` error is retryable when it looks connection- or timeout-related, mirroring
+    /// `HttpClient::should_retry_network`.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Timeout { .. } | Self::RateLimited { .. } => true,
+            Self::HttpError { status: Some(status), .. } => {
+                *status == 429 || (500..600).contains(status)
+            }
+            Self::Transport(err) => err.is_connect() || err.is_timeout() || err.is_request(),
+            _ => false,
+        }
+    }
+
+    /// How long a retry loop should wait before trying again, if the error carries that hint.
+    /// Only `RateLimited` (typically derived from a provider's `Retry-After` header) knows this;
+    /// every other variant returns `None`, leaving the caller's own backoff strategy in charge.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Returns a clone of this error safe to hand to a logger or error tracker. `message` and (on
+    /// [`Self::HttpError`]) `body` are run through [`redact_text`], the same sensitive-key
+    /// heuristics [`HttpClient`](crate::http::HttpClient) already uses to scrub its own
+    /// request/response logs, so a credential a provider echoed back in a JSON or form-encoded
+    /// payload (`api_key`, `signature`, `token`, ...) never reaches a log line.
+    ///
+    /// `Transport`/`Json`/`UrlParse` wrap a foreign error type this crate can't reconstruct from
+    /// scratch, so they're redacted into the plain variant that already shares their
+    /// `code()`/`hint()` (`HttpError`/`ParseError`/`ValidationError` respectively) instead of
+    /// losing that classification.
+    pub fn redacted(&self) -> Self {
+        let policy = RedactionPolicy::default();
+        match self {
+            Self::ConfigError { code, message, hint } => Self::ConfigError {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+            },
+            Self::ValidationError { code, message, hint } => Self::ValidationError {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+            },
+            Self::HttpError { code, message, hint, status, request_id, body, attempt } => {
+                Self::HttpError {
+                    code: *code,
+                    message: redact_text(message, &policy),
+                    hint: hint.clone(),
+                    status: *status,
+                    request_id: request_id.clone(),
+                    body: body.as_deref().map(|b| redact_text(b, &policy)),
+                    attempt: *attempt,
+                }
+            }
+            Self::ProviderError {
+                code,
+                message,
+                hint,
+                provider_code,
+                request_id,
+                debug_id,
+                details,
+                help_links,
+                error_type,
+            } => Self::ProviderError {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+                provider_code: provider_code.clone(),
+                request_id: request_id.clone(),
+                debug_id: debug_id.clone(),
+                details: details.clone(),
+                help_links: help_links.clone(),
+                error_type: *error_type,
+            },
+            Self::Unsupported { code, message, hint } => Self::Unsupported {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+            },
+            Self::ParseError { code, message, hint } => Self::ParseError {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+            },
+            Self::TimeoutError { code, message, hint, attempts } => Self::TimeoutError {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+                attempts: *attempts,
+            },
+            Self::PluginError { code, message, hint, module, operation } => Self::PluginError {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+                module: module.clone(),
+                operation: operation.clone(),
+            },
+            Self::Timeout { code, message, hint } => Self::Timeout {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+            },
+            Self::RateLimited { code, message, hint, retry_after } => Self::RateLimited {
+                code: *code,
+                message: redact_text(message, &policy),
+                hint: hint.clone(),
+                retry_after: *retry_after,
+            },
+            Self::Transport(source) => Self::HttpError {
+                code: ErrorCode::HttpFailure,
+                message: redact_text(&source.to_string(), &policy),
+                hint: self.hint().to_owned(),
+                status: source.status().map(|s| s.as_u16()),
+                request_id: None,
+                body: None,
+                attempt: 1,
+            },
+            Self::Json(source) => Self::ParseError {
+                code: ErrorCode::ParseFailed,
+                message: redact_text(&source.to_string(), &policy),
+                hint: self.hint().to_owned(),
+            },
+            Self::UrlParse(source) => Self::ValidationError {
+                code: ErrorCode::ValidationFailed,
+                message: redact_text(&source.to_string(), &policy),
+                hint: self.hint().to_owned(),
+            },
         }
     }
 
@@ -121,6 +351,7 @@ impl BdPaymentError {
         status: Option<u16>,
         request_id: Option<String>,
         body: Option<String>,
+        attempt: u32,
     ) -> Self {
         Self::HttpError {
             code: ErrorCode::HttpFailure,
@@ -129,6 +360,7 @@ impl BdPaymentError {
             status,
             request_id,
             body,
+            attempt,
         }
     }
 
@@ -144,6 +376,98 @@ impl BdPaymentError {
             hint: hint.into(),
             provider_code,
             request_id,
+            debug_id: None,
+            details: Vec::new(),
+            help_links: Vec::new(),
+            error_type: None,
+        }
+    }
+
+    /// Builds a [`Self::ProviderError`] from a provider's raw JSON error body, pulling out
+    /// whichever of PayPal's `debug_id`/`details`/`links` or Stripe's `type` shaped fields are
+    /// present instead of flattening everything into `message`. Unrecognized shapes just leave
+    /// the corresponding field empty/`None` rather than failing.
+    pub fn provider_from_json(provider_code: Option<String>, raw: &serde_json::Value) -> Self {
+        let message = raw
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .or_else(|| raw.get("error_description").and_then(serde_json::Value::as_str))
+            .or_else(|| raw.get("name").and_then(serde_json::Value::as_str))
+            .unwrap_or("Provider rejected the request.")
+            .to_owned();
+
+        let debug_id = raw
+            .get("debug_id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_owned);
+
+        let request_id = raw
+            .get("request_id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_owned);
+
+        let details = raw
+            .get("details")
+            .and_then(serde_json::Value::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let issue = entry
+                            .get("issue")
+                            .or_else(|| entry.get("code"))
+                            .and_then(serde_json::Value::as_str)?;
+                        Some(ProviderErrorDetail {
+                            field: entry
+                                .get("field")
+                                .or_else(|| entry.get("param"))
+                                .and_then(serde_json::Value::as_str)
+                                .map(str::to_owned),
+                            issue: issue.to_owned(),
+                            description: entry
+                                .get("description")
+                                .or_else(|| entry.get("message"))
+                                .and_then(serde_json::Value::as_str)
+                                .map(str::to_owned),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let help_links = raw
+            .get("links")
+            .and_then(serde_json::Value::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        entry
+                            .get("href")
+                            .and_then(serde_json::Value::as_str)
+                            .and_then(|href| Url::parse(href).ok())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let error_type = raw
+            .get("error_type")
+            .or_else(|| raw.get("type"))
+            .and_then(serde_json::Value::as_str)
+            .map(classify_provider_error_type);
+
+        Self::ProviderError {
+            code: ErrorCode::ProviderRejected,
+            message,
+            hint: "Inspect `details`/`help_links` on this error for provider-specific guidance."
+                .to_owned(),
+            provider_code,
+            request_id,
+            debug_id,
+            details,
+            help_links,
+            error_type,
         }
     }
 
@@ -162,4 +486,68 @@ impl BdPaymentError {
             hint: hint.into(),
         }
     }
+
+    pub fn timeout(message: impl Into<String>, hint: impl Into<String>, attempts: u32) -> Self {
+        Self::TimeoutError {
+            code: ErrorCode::PollTimeout,
+            message: message.into(),
+            hint: hint.into(),
+            attempts,
+        }
+    }
+
+    pub fn plugin(
+        module: impl Into<String>,
+        operation: impl Into<String>,
+        message: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self::PluginError {
+            code: ErrorCode::PluginFailure,
+            message: message.into(),
+            hint: hint.into(),
+            module: module.into(),
+            operation: operation.into(),
+        }
+    }
+
+    /// A request-level timeout, as opposed to [`Self::timeout`]'s polling-loop exhaustion.
+    pub fn request_timeout(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self::Timeout {
+            code: ErrorCode::RequestTimeout,
+            message: message.into(),
+            hint: hint.into(),
+        }
+    }
+
+    pub fn rate_limited(
+        message: impl Into<String>,
+        hint: impl Into<String>,
+        retry_after: Option<Duration>,
+    ) -> Self {
+        Self::RateLimited {
+            code: ErrorCode::RateLimited,
+            message: message.into(),
+            hint: hint.into(),
+            retry_after,
+        }
+    }
+}
+
+/// Maps a provider's own error-type string (PayPal's `error_type`, Stripe's `type`) onto
+/// [`ProviderErrorType`] via case-insensitive substring matching, since providers don't agree on
+/// exact spelling (`"invalid_request_error"` vs `"INVALID_REQUEST"`).
+fn classify_provider_error_type(raw: &str) -> ProviderErrorType {
+    let lower = raw.to_ascii_lowercase();
+    if lower.contains("auth") {
+        ProviderErrorType::Authentication
+    } else if lower.contains("rate") || lower.contains("throttle") {
+        ProviderErrorType::RateLimit
+    } else if lower.contains("unavailable") || lower.contains("down") {
+        ProviderErrorType::ProviderUnavailable
+    } else if lower.contains("invalid") || lower.contains("validation") {
+        ProviderErrorType::InvalidRequest
+    } else {
+        ProviderErrorType::Unknown
+    }
 }
@@ -1,15 +1,21 @@
 use std::{
     collections::BTreeMap,
+    future::Future,
     sync::Arc,
     time::{Duration, Instant},
 };
 
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::Rng;
 use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue},
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION},
     Method, StatusCode,
 };
-use serde::{de::DeserializeOwned, Serialize};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
+use tokio::sync::Mutex as AsyncMutex;
 use tokio::time::sleep;
 use tracing::{debug, warn};
 use uuid::Uuid;
@@ -18,6 +24,337 @@ use crate::{BdPaymentError, Result};
 
 const REDACTED: &str = "[REDACTED]";
 
+/// Mints a fresh bearer token for a gateway whose auth flow doesn't fit the client-credentials
+/// grant [`AuthStrategy::OAuth2ClientCredentials`] already covers — e.g. shurjoPay's
+/// username/password `/api/get_token` call, or aamarPay's tokenized checkout flow. Wrap an
+/// implementation in a [`CachingTokenProvider`] and plug it into [`AuthStrategy::TokenProvider`]
+/// to get the same caching, skew, and 401-triggered refresh behavior the built-in strategies get
+/// for free, instead of a provider crate reimplementing its own token cache.
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    /// Mints a new token, returning it alongside how long it remains valid for.
+    async fn mint(&self) -> Result<(SecretString, Duration)>;
+}
+
+#[derive(Clone)]
+struct CachedProviderToken {
+    token: SecretString,
+    expires_at: Instant,
+}
+
+/// Caches the token minted by a [`TokenProvider`] behind an `Arc<Mutex<>>`, reusing it until it's
+/// absent or within `skew` of expiry. [`HttpClient`] calls [`Self::token`] with `force_refresh`
+/// set after a `401`, which bypasses the cache check and overwrites the stored entry with a
+/// freshly minted token — the same pattern [`AuthStrategy::OAuth2ClientCredentials`]'s built-in
+/// cache uses.
+pub struct CachingTokenProvider {
+    provider: Arc<dyn TokenProvider>,
+    skew: Duration,
+    cached: AsyncMutex<Option<CachedProviderToken>>,
+}
+
+impl CachingTokenProvider {
+    /// Treats a cached token as expired 30 seconds before its real expiry, the same default skew
+    /// [`AuthStrategy::OAuth2ClientCredentials`]'s built-in cache uses.
+    pub fn new(provider: Arc<dyn TokenProvider>) -> Self {
+        Self::with_skew(provider, Duration::from_secs(30))
+    }
+
+    pub fn with_skew(provider: Arc<dyn TokenProvider>, skew: Duration) -> Self {
+        Self {
+            provider,
+            skew,
+            cached: AsyncMutex::new(None),
+        }
+    }
+
+    async fn token(&self, force_refresh: bool) -> Result<SecretString> {
+        let mut cache = self.cached.lock().await;
+        if !force_refresh {
+            if let Some(cached) = cache.as_ref() {
+                if Instant::now() < cached.expires_at {
+                    return Ok(cached.token.clone());
+                }
+            }
+        }
+
+        let (token, ttl) = self.provider.mint().await?;
+        *cache = Some(CachedProviderToken {
+            token: token.clone(),
+            expires_at: Instant::now() + ttl.saturating_sub(self.skew),
+        });
+        Ok(token)
+    }
+}
+
+/// The terminal outcome of a request recorded under one idempotency key, replayed instead of
+/// re-sending when the same key is reused.
+#[derive(Debug, Clone)]
+pub struct IdempotentResponse {
+    pub status: u16,
+    pub body: String,
+    pub request_id: Option<String>,
+}
+
+/// What [`IdempotencyStore::begin`] found when a caller claimed an idempotency key.
+pub enum IdempotencyClaim {
+    /// No prior record existed; the key is now claimed and the caller should send the request.
+    New,
+    /// Another caller already claimed this key and hasn't recorded a terminal result yet.
+    InFlight,
+    /// A prior call with this key already ran to completion; replay its result.
+    Completed(IdempotentResponse),
+}
+
+/// Backs [`HttpClient`]'s idempotency replay so a retried or duplicated request carrying the same
+/// `Idempotency-Key` header can't double-submit a payment.
+///
+/// [`HttpClient::request_json`]/[`HttpClient::post_form`] call [`Self::begin`] before sending a
+/// request that carries an idempotency key header, and [`Self::complete`] once they have a
+/// terminal HTTP response. [`InMemoryIdempotencyStore`] is the built-in, process-local default;
+/// the trait carries no in-process assumptions, so a Redis- or database-backed implementation that
+/// shares state across instances is a drop-in replacement.
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Atomically claims `key`: the first caller for a never-seen key gets [`IdempotencyClaim::New`]
+    /// and every other concurrent caller for that key sees [`IdempotencyClaim::InFlight`] until
+    /// [`Self::complete`] is called, after which it's [`IdempotencyClaim::Completed`].
+    async fn begin(&self, key: &str) -> IdempotencyClaim;
+
+    /// Records the terminal response for `key` so subsequent [`Self::begin`] calls replay it.
+    async fn complete(&self, key: &str, response: IdempotentResponse);
+}
+
+enum InMemoryIdempotencyEntry {
+    InFlight,
+    Completed(IdempotentResponse),
+}
+
+/// Process-local [`IdempotencyStore`] backed by a `HashMap`. Entries are never evicted, so a
+/// long-lived process that reuses idempotency keys unboundedly will grow this map — acceptable for
+/// the crate's default since idempotency keys are normally per-request UUIDs, but a multi-instance
+/// or long-running deployment with key reuse should supply a TTL'd or Redis-backed store instead.
+#[derive(Default)]
+pub struct InMemoryIdempotencyStore {
+    entries: AsyncMutex<std::collections::HashMap<String, InMemoryIdempotencyEntry>>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn begin(&self, key: &str) -> IdempotencyClaim {
+        let mut entries = self.entries.lock().await;
+        match entries.get(key) {
+            Some(InMemoryIdempotencyEntry::Completed(response)) => {
+                IdempotencyClaim::Completed(response.clone())
+            }
+            Some(InMemoryIdempotencyEntry::InFlight) => IdempotencyClaim::InFlight,
+            None => {
+                entries.insert(key.to_owned(), InMemoryIdempotencyEntry::InFlight);
+                IdempotencyClaim::New
+            }
+        }
+    }
+
+    async fn complete(&self, key: &str, response: IdempotentResponse) {
+        let mut entries = self.entries.lock().await;
+        entries.insert(key.to_owned(), InMemoryIdempotencyEntry::Completed(response));
+    }
+}
+
+/// How the [`HttpClient`] authenticates outbound requests.
+///
+/// `None` keeps today's behavior of leaving auth entirely to caller-supplied headers (what
+/// signature-based gateways like aamarPay/SSLCommerz use). `StaticBearer` is for providers with
+/// a fixed long-lived token. `OAuth2ClientCredentials` mints and caches a short-lived access
+/// token via the client-credentials grant, refreshing it on expiry or a `401` response.
+/// `TokenProvider` covers every other bearer-token flow via a caller-supplied
+/// [`CachingTokenProvider`], refreshed the same way.
+#[derive(Clone)]
+pub enum AuthStrategy {
+    None,
+    StaticBearer(SecretString),
+    OAuth2ClientCredentials {
+        token_url: url::Url,
+        client_id: String,
+        client_secret: SecretString,
+        scopes: Vec<String>,
+    },
+    TokenProvider(Arc<CachingTokenProvider>),
+}
+
+impl std::fmt::Debug for AuthStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::StaticBearer(_) => f.write_str("StaticBearer(SecretString([REDACTED]))"),
+            Self::OAuth2ClientCredentials {
+                token_url,
+                client_id,
+                scopes,
+                ..
+            } => f
+                .debug_struct("OAuth2ClientCredentials")
+                .field("token_url", token_url)
+                .field("client_id", client_id)
+                .field("client_secret", &REDACTED)
+                .field("scopes", scopes)
+                .finish(),
+            Self::TokenProvider(_) => f.write_str("TokenProvider(CachingTokenProvider)"),
+        }
+    }
+}
+
+impl Default for AuthStrategy {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+#[derive(Clone)]
+struct CachedToken {
+    access_token: SecretString,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct OAuth2TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct OAuth2TokenRequest<'a> {
+    grant_type: &'a str,
+    client_id: &'a str,
+    client_secret: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    scope: Vec<&'a str>,
+}
+
+/// How [`HttpClient`] schedules the delay between retry attempts when a response doesn't carry a
+/// `Retry-After` header.
+///
+/// `Exponential` is the crate's original `initial_backoff * 2^(attempt-1)` schedule, kept as the
+/// default so existing callers see no behavior change. `DecorrelatedJitter` instead draws each
+/// delay randomly between `initial_backoff` and `3x` the previous delay (the ["decorrelated
+/// jitter"](https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/) schedule),
+/// which avoids synchronized retry storms when many clients get rate-limited at once. `FullJitter`
+/// draws uniformly between zero and the same exponential ceiling `Exponential` would have slept
+/// for (`random_between(0, min(max_backoff, initial_backoff * 2^(attempt-1)))`), per the same
+/// article — it spreads retries out even further than `DecorrelatedJitter` at the cost of
+/// occasionally retrying almost immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackoffStrategy {
+    #[default]
+    Exponential,
+    DecorrelatedJitter,
+    FullJitter,
+}
+
+/// Bounds how deeply and how aggressively [`redact_json`]/`redact_headers` mask a logged
+/// request/response before it reaches [`HttpLogger`].
+///
+/// `sensitive_substrings`/`exact_keys` extend (never replace) the crate's built-in needle list, so
+/// a merchant onboarding a provider with its own secret field names (SSLCOMMERZ's `store_passwd`,
+/// a card processor's `card_number`/`cvv`) doesn't have to fork [`is_sensitive_key`]. `max_depth`
+/// caps how many nested object/array levels `redact_json` will descend before replacing the
+/// remaining subtree with `"[TRUNCATED]"` — `redact_json` itself never recurses (it's driven by an
+/// explicit work stack), so a maliciously deep payload can't overflow the call stack regardless of
+/// this setting, but a bound still keeps pathological payloads out of logs and log storage.
+/// `redact_pan_like_values`, when enabled, masks any string value that Luhn-checks as a card PAN
+/// even under a key name the substring/exact-key lists don't otherwise flag.
+#[derive(Debug, Clone)]
+pub struct RedactionPolicy {
+    pub sensitive_substrings: Vec<String>,
+    pub exact_keys: Vec<String>,
+    pub max_depth: usize,
+    pub redact_pan_like_values: bool,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self {
+            sensitive_substrings: SENSITIVE_KEY_SUBSTRINGS
+                .iter()
+                .map(|s| (*s).to_owned())
+                .collect(),
+            exact_keys: Vec::new(),
+            max_depth: 32,
+            redact_pan_like_values: false,
+        }
+    }
+}
+
+impl RedactionPolicy {
+    /// Adds a case-insensitive substring that, when contained in a key name, redacts that key's
+    /// value (on top of the built-in list).
+    pub fn with_sensitive_substring(mut self, substring: impl Into<String>) -> Self {
+        self.sensitive_substrings
+            .push(substring.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Adds a key name that redacts its value only on an exact (case-insensitive) match, for
+    /// field names too short or common to safely substring-match (e.g. a provider's `cvv`).
+    pub fn with_exact_key(mut self, key: impl Into<String>) -> Self {
+        self.exact_keys.push(key.into());
+        self
+    }
+
+    fn is_sensitive_key(&self, key: &str) -> bool {
+        let lower = key.to_ascii_lowercase();
+        self.exact_keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+            || self
+                .sensitive_substrings
+                .iter()
+                .any(|needle| lower.contains(needle.as_str()))
+    }
+}
+
+/// Which failures [`HttpClient`] treats as transient and worth retrying, on top of the shared
+/// `max_retries`/backoff budget in [`HttpSettings`]. The defaults match the crate's original,
+/// hardcoded behavior (429 and any 5xx; connect, timeout, or request-build `reqwest` errors), so
+/// built-in providers see no behavior change; a merchant whose gateway misuses a status code (e.g.
+/// treating a 409 as "try again") can extend `retryable_statuses` without forking `HttpClient`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub retryable_statuses: Vec<u16>,
+    pub retry_connect_errors: bool,
+    pub retry_timeout_errors: bool,
+    pub retry_request_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        let retryable_statuses = (500..600).chain(std::iter::once(429)).collect();
+        Self {
+            retryable_statuses,
+            retry_connect_errors: true,
+            retry_timeout_errors: true,
+            retry_request_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn allows_status(&self, status: StatusCode) -> bool {
+        self.retryable_statuses.contains(&status.as_u16())
+    }
+
+    fn allows_network_error(&self, err: &reqwest::Error) -> bool {
+        (self.retry_connect_errors && err.is_connect())
+            || (self.retry_timeout_errors && err.is_timeout())
+            || (self.retry_request_errors && err.is_request())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HttpSettings {
     pub timeout: Duration,
@@ -25,6 +362,10 @@ pub struct HttpSettings {
     pub initial_backoff: Duration,
     pub max_backoff: Duration,
     pub user_agent: String,
+    pub auth: AuthStrategy,
+    pub backoff_strategy: BackoffStrategy,
+    pub redaction: RedactionPolicy,
+    pub retry: RetryPolicy,
 }
 
 impl Default for HttpSettings {
@@ -35,6 +376,10 @@ impl Default for HttpSettings {
             initial_backoff: Duration::from_millis(200),
             max_backoff: Duration::from_secs(2),
             user_agent: format!("bd-payment-gateway/{}", env!("CARGO_PKG_VERSION")),
+            auth: AuthStrategy::None,
+            backoff_strategy: BackoffStrategy::default(),
+            redaction: RedactionPolicy::default(),
+            retry: RetryPolicy::default(),
         }
     }
 }
@@ -62,6 +407,8 @@ pub struct HttpClient {
     inner: reqwest::Client,
     settings: HttpSettings,
     logger: Option<Arc<dyn HttpLogger>>,
+    token_cache: Arc<AsyncMutex<Option<CachedToken>>>,
+    idempotency_store: Option<Arc<dyn IdempotencyStore>>,
 }
 
 impl HttpClient {
@@ -80,6 +427,8 @@ impl HttpClient {
             inner,
             settings,
             logger,
+            token_cache: Arc::new(AsyncMutex::new(None)),
+            idempotency_store: None,
         })
     }
 
@@ -87,6 +436,82 @@ impl HttpClient {
         Self::new(HttpSettings::default(), None)
     }
 
+    /// Enables idempotency replay: requests carrying an `Idempotency-Key` header (see
+    /// [`add_default_headers`]) are deduplicated against `store` instead of always hitting the
+    /// network. Off by default, since it requires callers to actually attach that header.
+    pub fn with_idempotency_store(mut self, store: Arc<dyn IdempotencyStore>) -> Self {
+        self.idempotency_store = Some(store);
+        self
+    }
+
+    /// Resolves the `Authorization` bearer token for the configured [`AuthStrategy`], minting and
+    /// caching an OAuth2 client-credentials token as needed. Returns `None` when `auth` is
+    /// `AuthStrategy::None`.
+    async fn bearer_token(&self, force_refresh: bool) -> Result<Option<SecretString>> {
+        match &self.settings.auth {
+            AuthStrategy::None => Ok(None),
+            AuthStrategy::StaticBearer(token) => Ok(Some(token.clone())),
+            AuthStrategy::OAuth2ClientCredentials {
+                token_url,
+                client_id,
+                client_secret,
+                scopes,
+            } => {
+                const EXPIRY_SKEW: Duration = Duration::from_secs(30);
+
+                let mut cache = self.token_cache.lock().await;
+                if !force_refresh {
+                    if let Some(cached) = cache.as_ref() {
+                        if Instant::now() < cached.expires_at {
+                            return Ok(Some(cached.access_token.clone()));
+                        }
+                    }
+                }
+
+                let body = OAuth2TokenRequest {
+                    grant_type: "client_credentials",
+                    client_id,
+                    client_secret: client_secret.expose_secret(),
+                    scope: scopes.iter().map(String::as_str).collect(),
+                };
+
+                let response: OAuth2TokenResponse = self
+                    .post_form_uncached(token_url, HeaderMap::new(), &body)
+                    .await?;
+
+                let expires_at = Instant::now()
+                    + Duration::from_secs(response.expires_in.unwrap_or(3600))
+                        .saturating_sub(EXPIRY_SKEW);
+                let access_token = SecretString::new(response.access_token.into());
+                *cache = Some(CachedToken {
+                    access_token: access_token.clone(),
+                    expires_at,
+                });
+
+                Ok(Some(access_token))
+            }
+            AuthStrategy::TokenProvider(caching) => {
+                Ok(Some(caching.token(force_refresh).await?))
+            }
+        }
+    }
+
+    async fn apply_auth(&self, headers: &mut HeaderMap, force_refresh: bool) -> Result<()> {
+        if let Some(token) = self.bearer_token(force_refresh).await? {
+            let value = format!("Bearer {}", token.expose_secret());
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&value).map_err(|e| {
+                    BdPaymentError::validation(
+                        format!("Invalid bearer token header value: {e}"),
+                        "Ensure the token only contains valid HTTP header characters.",
+                    )
+                })?,
+            );
+        }
+        Ok(())
+    }
+
     pub async fn get_json<R: DeserializeOwned>(
         &self,
         url: &url::Url,
@@ -111,6 +536,27 @@ impl HttpClient {
         url: &url::Url,
         headers: HeaderMap,
         form: &T,
+    ) -> Result<R> {
+        let mut headers = headers;
+        self.apply_auth(&mut headers, false).await?;
+        match self.post_form_uncached(url, headers.clone(), form).await {
+            Err(BdPaymentError::HttpError {
+                status: Some(401), ..
+            }) if !matches!(self.settings.auth, AuthStrategy::None) => {
+                self.apply_auth(&mut headers, true).await?;
+                self.post_form_uncached(url, headers, form).await
+            }
+            other => other,
+        }
+    }
+
+    /// Performs a single form POST without auth injection or 401-triggered refresh, used both by
+    /// `post_form` and internally to fetch OAuth2 tokens (which must not recurse into `apply_auth`).
+    async fn post_form_uncached<T: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &url::Url,
+        headers: HeaderMap,
+        form: &T,
     ) -> Result<R> {
         let mut headers = headers;
         headers.insert(
@@ -118,7 +564,7 @@ impl HttpClient {
             HeaderValue::from_static("application/x-www-form-urlencoded"),
         );
 
-        let redacted_headers = redact_headers(&headers);
+        let redacted_headers = redact_headers(&headers, &self.settings.redaction);
         let encoded_form = serde_urlencoded::to_string(form).map_err(|e| {
             BdPaymentError::validation(
                 format!("Failed to encode form body: {e}"),
@@ -126,7 +572,15 @@ impl HttpClient {
             )
         })?;
 
+        let idempotency_key = extract_idempotency_key(&headers);
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = self.idempotency_precheck(key).await {
+                return replay_idempotent_response::<R>(cached);
+            }
+        }
+
         let mut attempt = 0;
+        let mut prev_delay = self.settings.initial_backoff;
         loop {
             attempt += 1;
             let started = Instant::now();
@@ -151,6 +605,7 @@ impl HttpClient {
                 Ok(resp) => {
                     let status = resp.status();
                     let request_id = extract_request_id(resp.headers());
+                    let retry_after = parse_retry_after(resp.headers());
                     let text = resp.text().await.map_err(|e| {
                         BdPaymentError::http(
                             format!("Unable to read HTTP response body: {e}"),
@@ -158,6 +613,7 @@ impl HttpClient {
                             None,
                             request_id.clone(),
                             None,
+                            attempt,
                         )
                     })?;
 
@@ -174,22 +630,35 @@ impl HttpClient {
 
                     if status.is_success() {
                         self.log_response(&log);
+                        self.complete_idempotency(&idempotency_key, status.as_u16(), &text, request_id.clone())
+                            .await;
                         return parse_json::<R>(&text, request_id.clone());
                     }
 
                     if self.should_retry_status(status, attempt) {
                         self.log_retry(&log, "retryable HTTP status");
-                        self.wait_backoff(attempt).await;
+                        self.wait_backoff(attempt, &mut prev_delay, retry_after).await;
                         continue;
                     }
 
                     self.log_response(&log);
+                    let truncated_body = truncate(&text, 1024);
+                    if !self.will_retry_with_refreshed_auth(status) {
+                        self.complete_idempotency(
+                            &idempotency_key,
+                            status.as_u16(),
+                            &truncated_body,
+                            request_id.clone(),
+                        )
+                        .await;
+                    }
                     return Err(BdPaymentError::http(
                         format!("HTTP {} calling {}", status.as_u16(), url),
                         "Verify API credentials, endpoint environment (sandbox/live), and payload fields.",
                         Some(status.as_u16()),
                         request_id,
-                        Some(truncate(&text, 1024)),
+                        Some(truncated_body),
+                        attempt,
                     ));
                 }
                 Err(err) => {
@@ -205,7 +674,7 @@ impl HttpClient {
                     };
                     if self.should_retry_network(&err, attempt) {
                         self.log_retry(&log, "network error");
-                        self.wait_backoff(attempt).await;
+                        self.wait_backoff(attempt, &mut prev_delay, None).await;
                         continue;
                     }
                     return Err(BdPaymentError::http(
@@ -214,6 +683,7 @@ impl HttpClient {
                         None,
                         None,
                         None,
+                        attempt,
                     ));
                 }
             }
@@ -226,6 +696,29 @@ impl HttpClient {
         url: &url::Url,
         headers: HeaderMap,
         body: Option<&T>,
+    ) -> Result<R> {
+        let mut headers = headers;
+        self.apply_auth(&mut headers, false).await?;
+        match self
+            .request_json_uncached(method.clone(), url, headers.clone(), body)
+            .await
+        {
+            Err(BdPaymentError::HttpError {
+                status: Some(401), ..
+            }) if !matches!(self.settings.auth, AuthStrategy::None) => {
+                self.apply_auth(&mut headers, true).await?;
+                self.request_json_uncached(method, url, headers, body).await
+            }
+            other => other,
+        }
+    }
+
+    async fn request_json_uncached<T: Serialize, R: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &url::Url,
+        headers: HeaderMap,
+        body: Option<&T>,
     ) -> Result<R> {
         let serialized_body = body.map(serde_json::to_value).transpose().map_err(|e| {
             BdPaymentError::validation(
@@ -234,10 +727,20 @@ impl HttpClient {
             )
         })?;
 
-        let redacted_body = serialized_body.as_ref().map(redact_json);
-        let redacted_headers = redact_headers(&headers);
+        let redacted_body = serialized_body
+            .as_ref()
+            .map(|v| redact_json(v, &self.settings.redaction));
+        let redacted_headers = redact_headers(&headers, &self.settings.redaction);
+
+        let idempotency_key = extract_idempotency_key(&headers);
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = self.idempotency_precheck(key).await {
+                return replay_idempotent_response::<R>(cached);
+            }
+        }
 
         let mut attempt = 0;
+        let mut prev_delay = self.settings.initial_backoff;
         loop {
             attempt += 1;
             let started = Instant::now();
@@ -266,6 +769,7 @@ impl HttpClient {
                 Ok(resp) => {
                     let status = resp.status();
                     let request_id = extract_request_id(resp.headers());
+                    let retry_after = parse_retry_after(resp.headers());
                     let text = resp.text().await.map_err(|e| {
                         BdPaymentError::http(
                             format!("Unable to read HTTP response body: {e}"),
@@ -273,6 +777,7 @@ impl HttpClient {
                             None,
                             request_id.clone(),
                             None,
+                            attempt,
                         )
                     })?;
 
@@ -289,22 +794,35 @@ impl HttpClient {
 
                     if status.is_success() {
                         self.log_response(&log);
+                        self.complete_idempotency(&idempotency_key, status.as_u16(), &text, request_id.clone())
+                            .await;
                         return parse_json::<R>(&text, request_id.clone());
                     }
 
                     if self.should_retry_status(status, attempt) {
                         self.log_retry(&log, "retryable HTTP status");
-                        self.wait_backoff(attempt).await;
+                        self.wait_backoff(attempt, &mut prev_delay, retry_after).await;
                         continue;
                     }
 
                     self.log_response(&log);
+                    let truncated_body = truncate(&text, 1024);
+                    if !self.will_retry_with_refreshed_auth(status) {
+                        self.complete_idempotency(
+                            &idempotency_key,
+                            status.as_u16(),
+                            &truncated_body,
+                            request_id.clone(),
+                        )
+                        .await;
+                    }
                     return Err(BdPaymentError::http(
                         format!("HTTP {} calling {}", status.as_u16(), url),
                         "Verify API credentials, endpoint environment (sandbox/live), and payload fields.",
                         Some(status.as_u16()),
                         request_id,
-                        Some(truncate(&text, 1024)),
+                        Some(truncated_body),
+                        attempt,
                     ));
                 }
                 Err(err) => {
@@ -320,7 +838,7 @@ impl HttpClient {
                     };
                     if self.should_retry_network(&err, attempt) {
                         self.log_retry(&log, "network error");
-                        self.wait_backoff(attempt).await;
+                        self.wait_backoff(attempt, &mut prev_delay, None).await;
                         continue;
                     }
                     return Err(BdPaymentError::http(
@@ -329,6 +847,7 @@ impl HttpClient {
                         None,
                         None,
                         None,
+                        attempt,
                     ));
                 }
             }
@@ -336,25 +855,126 @@ impl HttpClient {
     }
 
     fn should_retry_status(&self, status: StatusCode, attempt: u32) -> bool {
-        attempt <= self.settings.max_retries
-            && (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error())
+        attempt <= self.settings.max_retries && self.settings.retry.allows_status(status)
+    }
+
+    /// Whether `post_form`/`request_json` will re-send this request with a refreshed token after
+    /// seeing `status`, i.e. the same condition those wrappers use to decide to retry on 401. The
+    /// `_uncached` loop must not call [`Self::complete_idempotency`] for a result that's about to be
+    /// superseded by a retried attempt, or the retry's own `idempotency_precheck` would immediately
+    /// replay this stale 401 instead of actually resending with the new token.
+    fn will_retry_with_refreshed_auth(&self, status: StatusCode) -> bool {
+        status == StatusCode::UNAUTHORIZED && !matches!(self.settings.auth, AuthStrategy::None)
     }
 
     fn should_retry_network(&self, err: &reqwest::Error, attempt: u32) -> bool {
-        attempt <= self.settings.max_retries
-            && (err.is_connect() || err.is_timeout() || err.is_request())
+        attempt <= self.settings.max_retries && self.settings.retry.allows_network_error(err)
     }
 
-    async fn wait_backoff(&self, attempt: u32) {
-        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
-        let backoff = self
-            .settings
-            .initial_backoff
-            .saturating_mul(factor)
-            .min(self.settings.max_backoff);
+    /// Sleeps for the next retry delay and advances `prev_delay` for the next call. Honors a
+    /// provider's `Retry-After` header when present (capped at `max_backoff`); otherwise computes
+    /// the delay per `self.settings.backoff_strategy`.
+    async fn wait_backoff(&self, attempt: u32, prev_delay: &mut Duration, retry_after: Option<Duration>) {
+        let backoff = if let Some(retry_after) = retry_after {
+            retry_after.min(self.settings.max_backoff)
+        } else {
+            match self.settings.backoff_strategy {
+                BackoffStrategy::Exponential => {
+                    let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+                    self.settings
+                        .initial_backoff
+                        .saturating_mul(factor)
+                        .min(self.settings.max_backoff)
+                }
+                BackoffStrategy::DecorrelatedJitter => {
+                    let initial_nanos = self.settings.initial_backoff.as_nanos();
+                    let upper_nanos = prev_delay
+                        .saturating_mul(3)
+                        .as_nanos()
+                        .max(initial_nanos);
+                    let delay_nanos = if upper_nanos <= initial_nanos {
+                        initial_nanos
+                    } else {
+                        rand::thread_rng().gen_range(initial_nanos..=upper_nanos)
+                    };
+                    let delay = Duration::from_nanos(delay_nanos.min(u64::MAX as u128) as u64)
+                        .min(self.settings.max_backoff);
+                    *prev_delay = delay;
+                    delay
+                }
+                BackoffStrategy::FullJitter => {
+                    let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+                    let ceiling = self
+                        .settings
+                        .initial_backoff
+                        .saturating_mul(factor)
+                        .min(self.settings.max_backoff);
+                    let delay = if ceiling.is_zero() {
+                        ceiling
+                    } else {
+                        let nanos = rand::thread_rng().gen_range(0..=ceiling.as_nanos());
+                        Duration::from_nanos(nanos.min(u64::MAX as u128) as u64)
+                    };
+                    *prev_delay = delay;
+                    delay
+                }
+            }
+        };
         sleep(backoff).await;
     }
 
+    /// Consults the configured [`IdempotencyStore`] for `key` before a request is sent. Returns
+    /// `Some` with the cached terminal result if one already exists, in which case the caller must
+    /// skip sending entirely. If another call for the same key is currently in flight, polls a few
+    /// times rather than sending a concurrent duplicate; if it's still in flight once polling gives
+    /// up, returns `None` so the caller proceeds and [`Self::complete_idempotency`] records
+    /// whichever result actually lands last.
+    async fn idempotency_precheck(&self, key: &str) -> Option<IdempotentResponse> {
+        const MAX_POLLS: u32 = 5;
+        const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+        let store = self.idempotency_store.as_ref()?;
+        for attempt in 0..=MAX_POLLS {
+            match store.begin(key).await {
+                IdempotencyClaim::Completed(response) => return Some(response),
+                IdempotencyClaim::New => return None,
+                IdempotencyClaim::InFlight => {
+                    if attempt == MAX_POLLS {
+                        return None;
+                    }
+                    sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+        None
+    }
+
+    /// Records the terminal HTTP result for `key` (if idempotency is configured and the request
+    /// carried a key), so a retry or a fresh call reusing it replays this result instead of
+    /// re-sending. Only called for a response that actually reached the provider; a pure network
+    /// failure with no HTTP status is never cached, since there's no proof the provider processed
+    /// anything.
+    async fn complete_idempotency(
+        &self,
+        key: &Option<String>,
+        status: u16,
+        body: &str,
+        request_id: Option<String>,
+    ) {
+        if let (Some(store), Some(key)) = (&self.idempotency_store, key) {
+            store
+                .complete(
+                    key,
+                    IdempotentResponse {
+                        status,
+                        body: body.to_owned(),
+                        request_id,
+                    },
+                )
+                .await;
+        }
+    }
+
     fn log_request(&self, record: &HttpLogRecord) {
         debug!(
             method = %record.method,
@@ -416,6 +1036,48 @@ fn extract_request_id(headers: &HeaderMap) -> Option<String> {
         .map(ToOwned::to_owned)
 }
 
+/// Reads the `Idempotency-Key` header [`add_default_headers`] attaches, the key
+/// [`HttpClient`]'s idempotency replay is keyed on.
+fn extract_idempotency_key(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("idempotency-key")
+        .and_then(|v| v.to_str().ok())
+        .map(ToOwned::to_owned)
+}
+
+/// Reconstructs a `Result<R>` from a cached [`IdempotentResponse`] the same way the live request
+/// path would have returned it the first time: a 2xx status is parsed as `R`, anything else
+/// becomes the same [`BdPaymentError::HttpError`] a fresh failing call would produce.
+fn replay_idempotent_response<R: DeserializeOwned>(cached: IdempotentResponse) -> Result<R> {
+    if (200..300).contains(&cached.status) {
+        parse_json::<R>(&cached.body, cached.request_id)
+    } else {
+        Err(BdPaymentError::http(
+            format!("HTTP {} (replayed from idempotency cache)", cached.status),
+            "This result was replayed from an earlier attempt with the same idempotency key; it was not re-sent.",
+            Some(cached.status),
+            cached.request_id,
+            Some(cached.body),
+            0,
+        ))
+    }
+}
+
+/// Parses a `Retry-After` header as either delta-seconds (`"120"`) or an HTTP-date
+/// (`"Tue, 15 Nov 1994 08:12:31 GMT"`), per RFC 7231 section 7.1.3. Returns `None` for a missing
+/// or unparseable header, or a date that's already in the past.
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let raw = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = raw.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(raw.trim()).ok()?;
+    let delta = date.with_timezone(&Utc) - Utc::now();
+    delta.to_std().ok()
+}
+
 pub fn generate_correlation_id() -> String {
     Uuid::now_v7().to_string()
 }
@@ -456,12 +1118,22 @@ pub fn add_default_headers(
     Ok(headers)
 }
 
-fn redact_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+const SENSITIVE_KEY_SUBSTRINGS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "authorization",
+    "key",
+    "store_id",
+    "signature",
+];
+
+fn redact_headers(headers: &HeaderMap, policy: &RedactionPolicy) -> BTreeMap<String, String> {
     headers
         .iter()
         .map(|(k, v)| {
             let key = k.as_str().to_owned();
-            let value = if is_sensitive_key(k.as_str()) {
+            let value = if policy.is_sensitive_key(k.as_str()) {
                 REDACTED.to_owned()
             } else {
                 v.to_str().unwrap_or("<binary>").to_owned()
@@ -471,37 +1143,238 @@ fn redact_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
         .collect()
 }
 
-pub fn redact_json(value: &Value) -> Value {
-    match value {
-        Value::Object(map) => Value::Object(
-            map.iter()
-                .map(|(k, v)| {
-                    if is_sensitive_key(k) {
-                        (k.clone(), Value::String(REDACTED.to_owned()))
-                    } else {
-                        (k.clone(), redact_json(v))
+enum RedactFrame {
+    Array {
+        remaining: std::vec::IntoIter<Value>,
+        built: Vec<Value>,
+        depth: usize,
+    },
+    Object {
+        remaining: std::vec::IntoIter<(String, Value)>,
+        built: serde_json::Map<String, Value>,
+        key: String,
+        depth: usize,
+    },
+}
+
+/// Advances an object's entries, redacting sensitive keys and leaf values inline, stopping as soon
+/// as a nested object/array is found (so the caller can descend into it without recursing here).
+/// Returns `Some((key, child, child_depth))` for that nested child, or `None` once `built` holds
+/// the fully-resolved object.
+fn object_step(
+    remaining: &mut std::vec::IntoIter<(String, Value)>,
+    built: &mut serde_json::Map<String, Value>,
+    depth: usize,
+    policy: &RedactionPolicy,
+) -> Option<(String, Value, usize)> {
+    for (k, v) in remaining.by_ref() {
+        if policy.is_sensitive_key(&k) {
+            built.insert(k, Value::String(REDACTED.to_owned()));
+            continue;
+        }
+        if matches!(v, Value::Object(_) | Value::Array(_)) {
+            return Some((k, v, depth + 1));
+        }
+        built.insert(k, redact_leaf_value(&v, policy));
+    }
+    None
+}
+
+/// Array counterpart of [`object_step`]: redacts leaf values inline and stops at the first nested
+/// container so the caller can descend without recursing.
+fn array_step(
+    remaining: &mut std::vec::IntoIter<Value>,
+    built: &mut Vec<Value>,
+    depth: usize,
+    policy: &RedactionPolicy,
+) -> Option<(Value, usize)> {
+    for v in remaining.by_ref() {
+        if matches!(v, Value::Object(_) | Value::Array(_)) {
+            return Some((v, depth + 1));
+        }
+        built.push(redact_leaf_value(&v, policy));
+    }
+    None
+}
+
+fn redact_leaf_value(value: &Value, policy: &RedactionPolicy) -> Value {
+    if policy.redact_pan_like_values {
+        if let Value::String(s) = value {
+            if looks_like_pan(s) {
+                return Value::String(REDACTED.to_owned());
+            }
+        }
+    }
+    value.clone()
+}
+
+/// Reports whether `s` is 13-19 digits (ignoring spaces/hyphens) that pass the Luhn checksum —
+/// the shape of a card PAN, independent of any key name it happens to be stored under.
+fn looks_like_pan(s: &str) -> bool {
+    if !s.chars().all(|c| c.is_ascii_digit() || c == ' ' || c == '-') {
+        return false;
+    }
+    let digits: Vec<u32> = s.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() < 13 || digits.len() > 19 {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Redacts sensitive fields (per `policy`) from a JSON value for safe logging.
+///
+/// Driven by an explicit work stack rather than recursing, so a deeply/adversarially nested
+/// payload can't blow the call stack; past `policy.max_depth` a subtree is replaced wholesale with
+/// `"[TRUNCATED]"` instead of being descended into.
+pub fn redact_json(value: &Value, policy: &RedactionPolicy) -> Value {
+    let mut stack: Vec<RedactFrame> = Vec::new();
+    let mut current = value.clone();
+    let mut depth = 0usize;
+
+    loop {
+        let mut finished = match current {
+            Value::Object(map) if !map.is_empty() && depth >= policy.max_depth => {
+                Value::String("[TRUNCATED]".to_owned())
+            }
+            Value::Array(items) if !items.is_empty() && depth >= policy.max_depth => {
+                Value::String("[TRUNCATED]".to_owned())
+            }
+            Value::Object(map) => {
+                let mut remaining = map.into_iter().collect::<Vec<_>>().into_iter();
+                let mut built = serde_json::Map::new();
+                match object_step(&mut remaining, &mut built, depth, policy) {
+                    Some((key, child, child_depth)) => {
+                        stack.push(RedactFrame::Object {
+                            remaining,
+                            built,
+                            key,
+                            depth,
+                        });
+                        current = child;
+                        depth = child_depth;
+                        continue;
                     }
-                })
-                .collect(),
-        ),
-        Value::Array(values) => Value::Array(values.iter().map(redact_json).collect()),
-        _ => value.clone(),
+                    None => Value::Object(built),
+                }
+            }
+            Value::Array(items) => {
+                let mut remaining = items.into_iter().collect::<Vec<_>>().into_iter();
+                let mut built = Vec::new();
+                match array_step(&mut remaining, &mut built, depth, policy) {
+                    Some((child, child_depth)) => {
+                        stack.push(RedactFrame::Array {
+                            remaining,
+                            built,
+                            depth,
+                        });
+                        current = child;
+                        depth = child_depth;
+                        continue;
+                    }
+                    None => Value::Array(built),
+                }
+            }
+            other => redact_leaf_value(&other, policy),
+        };
+
+        loop {
+            match stack.pop() {
+                None => return finished,
+                Some(RedactFrame::Object {
+                    mut remaining,
+                    mut built,
+                    key,
+                    depth: parent_depth,
+                }) => {
+                    built.insert(key, finished);
+                    match object_step(&mut remaining, &mut built, parent_depth, policy) {
+                        Some((key, child, child_depth)) => {
+                            stack.push(RedactFrame::Object {
+                                remaining,
+                                built,
+                                key,
+                                depth: parent_depth,
+                            });
+                            current = child;
+                            depth = child_depth;
+                            break;
+                        }
+                        None => {
+                            finished = Value::Object(built);
+                            continue;
+                        }
+                    }
+                }
+                Some(RedactFrame::Array {
+                    mut remaining,
+                    mut built,
+                    depth: parent_depth,
+                }) => {
+                    built.push(finished);
+                    match array_step(&mut remaining, &mut built, parent_depth, policy) {
+                        Some((child, child_depth)) => {
+                            stack.push(RedactFrame::Array {
+                                remaining,
+                                built,
+                                depth: parent_depth,
+                            });
+                            current = child;
+                            depth = child_depth;
+                            break;
+                        }
+                        None => {
+                            finished = Value::Array(built);
+                            continue;
+                        }
+                    }
+                }
+            }
+        }
     }
 }
 
-fn is_sensitive_key(key: &str) -> bool {
-    let key = key.to_ascii_lowercase();
-    [
-        "token",
-        "secret",
-        "password",
-        "authorization",
-        "key",
-        "store_id",
-        "signature",
-    ]
-    .iter()
-    .any(|needle| key.contains(needle))
+pub(crate) fn is_sensitive_key(key: &str) -> bool {
+    RedactionPolicy::default().is_sensitive_key(key)
+}
+
+/// Best-effort credential scrubbing for a blob of text whose shape isn't known up front, for
+/// callers (like [`BdPaymentError::redacted`](crate::error::BdPaymentError::redacted)) that only
+/// have a response/error body as a raw `&str` rather than an already-parsed [`Value`]. JSON text
+/// gets the full [`redact_json`] treatment; anything else is treated as `&`-separated `key=value`
+/// pairs, and the value half of each pair whose key matches `policy` is masked. Other shapes (such
+/// as `key: value` lines) are returned unchanged.
+pub fn redact_text(text: &str, policy: &RedactionPolicy) -> String {
+    if let Ok(value) = serde_json::from_str::<Value>(text) {
+        return redact_json(&value, policy).to_string();
+    }
+    redact_key_value_pairs(text, policy)
+}
+
+fn redact_key_value_pairs(text: &str, policy: &RedactionPolicy) -> String {
+    text.split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if policy.is_sensitive_key(key) => format!("{key}={REDACTED}"),
+            _ => pair.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
 }
 
 fn truncate(s: &str, max_chars: usize) -> String {
@@ -512,11 +1385,100 @@ fn truncate(s: &str, max_chars: usize) -> String {
     format!("{truncated}...")
 }
 
+struct CachedBearerToken {
+    token: SecretString,
+    expires_at: DateTime<Utc>,
+}
+
+/// A reusable, provider-agnostic cache for a bearer token that's minted by POSTing credentials to
+/// an `/authorize`-style endpoint and expires after some `expires_in`, the pattern several
+/// regional gateways and the PayU flow use instead of a per-request signature like PortWallet's.
+/// A provider composes one of these the way `PortwalletClient` composes its signature
+/// helper, supplying its own token endpoint/body shape via the `mint` closure passed to
+/// [`TokenManager::bearer`].
+///
+/// The mint only runs while the lock is held, so concurrent callers single-flight onto one
+/// in-flight refresh instead of all stampeding the token endpoint when the cached token expires.
+pub struct TokenManager {
+    skew: Duration,
+    cached: AsyncMutex<Option<CachedBearerToken>>,
+}
+
+impl TokenManager {
+    /// Treats a cached token as expired 60 seconds before its real `expires_at` (the built-in
+    /// [`AuthStrategy::OAuth2ClientCredentials`] caching uses a 30 second skew instead).
+    pub fn new() -> Self {
+        Self::with_skew(Duration::from_secs(60))
+    }
+
+    pub fn with_skew(skew: Duration) -> Self {
+        Self {
+            skew,
+            cached: AsyncMutex::new(None),
+        }
+    }
+
+    /// Returns an `Authorization: Bearer <token>` header value, reusing the cached token while
+    /// `Utc::now() < expires_at - skew`, otherwise calling `mint` under the lock to fetch a fresh
+    /// `(token, expires_at)` pair and caching it.
+    pub async fn bearer<F, Fut>(&self, mint: F) -> Result<HeaderValue>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(SecretString, DateTime<Utc>)>>,
+    {
+        let mut cache = self.cached.lock().await;
+        if let Some(cached) = cache.as_ref() {
+            if Utc::now() < cached.expires_at - self.skew {
+                return bearer_header(&cached.token);
+            }
+        }
+
+        let (token, expires_at) = mint().await?;
+        let header = bearer_header(&token)?;
+        *cache = Some(CachedBearerToken { token, expires_at });
+        Ok(header)
+    }
+}
+
+impl Default for TokenManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn bearer_header(token: &SecretString) -> Result<HeaderValue> {
+    let value = format!("Bearer {}", token.expose_secret());
+    HeaderValue::from_str(&value).map_err(|e| {
+        BdPaymentError::validation(
+            format!("Invalid bearer token header value: {e}"),
+            "Ensure the token only contains valid HTTP header characters.",
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use secrecy::{ExposeSecret, SecretString};
     use serde_json::json;
 
-    use super::redact_json;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use chrono::{Duration as ChronoDuration, Utc};
+
+    use async_trait::async_trait;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    use super::{
+        extract_idempotency_key, parse_retry_after, redact_json, redact_text,
+        replay_idempotent_response, AuthStrategy, BackoffStrategy, CachingTokenProvider,
+        HttpClient, HttpSettings, IdempotencyClaim, IdempotencyStore, IdempotentResponse,
+        InMemoryIdempotencyStore, RedactionPolicy, RetryPolicy, TokenManager, TokenProvider,
+    };
+    use crate::Result;
+    use std::sync::Arc;
 
     #[test]
     fn redacts_sensitive_json_fields() {
@@ -525,9 +1487,459 @@ mod tests {
             "nested": {"token": "abc", "visible": "ok"}
         });
 
-        let redacted = redact_json(&value);
+        let redacted = redact_json(&value, &RedactionPolicy::default());
         assert_eq!(redacted["api_key"], "[REDACTED]");
         assert_eq!(redacted["nested"]["token"], "[REDACTED]");
         assert_eq!(redacted["nested"]["visible"], "ok");
     }
+
+    #[test]
+    fn redact_json_truncates_beyond_max_depth_instead_of_recursing() {
+        let mut value = json!({"leaf": "visible"});
+        for _ in 0..10 {
+            value = json!({"child": value});
+        }
+
+        let policy = RedactionPolicy {
+            max_depth: 3,
+            ..RedactionPolicy::default()
+        };
+        let redacted = redact_json(&value, &policy);
+
+        // Three levels of `child` are preserved before the remaining subtree is truncated.
+        let truncated = &redacted["child"]["child"]["child"];
+        assert_eq!(*truncated, serde_json::Value::String("[TRUNCATED]".to_owned()));
+    }
+
+    #[test]
+    fn redact_json_honors_custom_sensitive_substring_and_exact_key() {
+        let value = json!({"store_passwd": "shh", "cvv": "123", "visible": "ok"});
+        let policy = RedactionPolicy::default()
+            .with_sensitive_substring("passwd")
+            .with_exact_key("cvv");
+        let redacted = redact_json(&value, &policy);
+
+        assert_eq!(redacted["store_passwd"], "[REDACTED]");
+        assert_eq!(redacted["cvv"], "[REDACTED]");
+        assert_eq!(redacted["visible"], "ok");
+    }
+
+    #[test]
+    fn redact_json_masks_pan_like_values_when_enabled() {
+        let value = json!({"card_number": "4242424242424242", "note": "4242424242424242"});
+        let policy = RedactionPolicy {
+            redact_pan_like_values: true,
+            ..RedactionPolicy::default()
+        };
+        let redacted = redact_json(&value, &policy);
+
+        // Neither key name matches the default substring list; both values are redacted purely
+        // because they Luhn-check as a PAN.
+        assert_eq!(redacted["card_number"], "[REDACTED]");
+        assert_eq!(redacted["note"], "[REDACTED]");
+    }
+
+    #[test]
+    fn redact_text_redacts_json_bodies_via_redact_json() {
+        let body = r#"{"api_key": "live_123", "status": "failed"}"#;
+        let redacted = redact_text(body, &RedactionPolicy::default());
+
+        assert!(redacted.contains("[REDACTED]"));
+        assert!(!redacted.contains("live_123"));
+        assert!(redacted.contains("failed"));
+    }
+
+    #[test]
+    fn redact_text_masks_sensitive_keys_in_form_encoded_bodies() {
+        let body = "status=failed&api_key=live_123&note=ok";
+        let redacted = redact_text(body, &RedactionPolicy::default());
+
+        assert_eq!(redacted, "status=failed&api_key=[REDACTED]&note=ok");
+    }
+
+    #[tokio::test]
+    async fn static_bearer_token_is_returned_without_network_call() {
+        let settings = HttpSettings {
+            auth: AuthStrategy::StaticBearer(SecretString::new("tok_abc".to_owned().into())),
+            ..HttpSettings::default()
+        };
+        let client = HttpClient::new(settings, None).expect("client");
+
+        let token = client
+            .bearer_token(false)
+            .await
+            .expect("static bearer resolves")
+            .expect("token should be present");
+
+        assert_eq!(token.expose_secret(), "tok_abc");
+    }
+
+    #[tokio::test]
+    async fn no_auth_strategy_yields_no_token() {
+        let client = HttpClient::with_default_settings().expect("client");
+        let token = client
+            .bearer_token(false)
+            .await
+            .expect("none resolves without error");
+        assert!(token.is_none());
+    }
+
+    #[tokio::test]
+    async fn token_manager_reuses_cached_token_until_skew_expiry() {
+        let manager = TokenManager::with_skew(Duration::from_secs(60));
+        let mints = AtomicU32::new(0);
+
+        for _ in 0..3 {
+            let header = manager
+                .bearer(|| async {
+                    mints.fetch_add(1, Ordering::SeqCst);
+                    Ok((
+                        SecretString::new("tok_live".to_owned().into()),
+                        Utc::now() + ChronoDuration::seconds(3600),
+                    ))
+                })
+                .await
+                .expect("mint succeeds");
+            assert_eq!(header, "Bearer tok_live");
+        }
+
+        assert_eq!(mints.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn token_manager_remints_once_cached_token_is_within_skew_of_expiry() {
+        let manager = TokenManager::with_skew(Duration::from_secs(60));
+
+        manager
+            .bearer(|| async {
+                Ok((
+                    SecretString::new("tok_old".to_owned().into()),
+                    Utc::now() + ChronoDuration::seconds(30),
+                ))
+            })
+            .await
+            .expect("first mint succeeds");
+
+        let header = manager
+            .bearer(|| async {
+                Ok((
+                    SecretString::new("tok_new".to_owned().into()),
+                    Utc::now() + ChronoDuration::seconds(3600),
+                ))
+            })
+            .await
+            .expect("remint succeeds");
+
+        assert_eq!(header, "Bearer tok_new");
+    }
+
+    struct CountingTokenProvider {
+        mints: AtomicU32,
+        ttl: Duration,
+    }
+
+    #[async_trait]
+    impl TokenProvider for CountingTokenProvider {
+        async fn mint(&self) -> Result<(SecretString, Duration)> {
+            self.mints.fetch_add(1, Ordering::SeqCst);
+            Ok((
+                SecretString::new(format!("tok_{}", self.mints.load(Ordering::SeqCst)).into()),
+                self.ttl,
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn caching_token_provider_reuses_token_until_skew_expiry() {
+        let provider = Arc::new(CountingTokenProvider {
+            mints: AtomicU32::new(0),
+            ttl: Duration::from_secs(3600),
+        });
+        let caching = CachingTokenProvider::with_skew(provider.clone(), Duration::from_secs(60));
+
+        for _ in 0..3 {
+            let token = caching.token(false).await.expect("token resolves");
+            assert_eq!(token.expose_secret(), "tok_1");
+        }
+
+        assert_eq!(provider.mints.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn caching_token_provider_remints_on_force_refresh() {
+        let provider = Arc::new(CountingTokenProvider {
+            mints: AtomicU32::new(0),
+            ttl: Duration::from_secs(3600),
+        });
+        let caching = CachingTokenProvider::new(provider.clone());
+
+        let first = caching.token(false).await.expect("first mint succeeds");
+        assert_eq!(first.expose_secret(), "tok_1");
+
+        let second = caching
+            .token(true)
+            .await
+            .expect("forced refresh mints again");
+        assert_eq!(second.expose_secret(), "tok_2");
+        assert_eq!(provider.mints.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn token_provider_auth_strategy_resolves_through_http_client() {
+        let provider = Arc::new(CountingTokenProvider {
+            mints: AtomicU32::new(0),
+            ttl: Duration::from_secs(3600),
+        });
+        let settings = HttpSettings {
+            auth: AuthStrategy::TokenProvider(Arc::new(CachingTokenProvider::new(provider))),
+            ..HttpSettings::default()
+        };
+        let client = HttpClient::new(settings, None).expect("client");
+
+        let token = client
+            .bearer_token(false)
+            .await
+            .expect("token provider resolves")
+            .expect("token should be present");
+        assert_eq!(token.expose_secret(), "tok_1");
+    }
+
+    #[test]
+    fn parse_retry_after_reads_delta_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("120"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn parse_retry_after_reads_http_date() {
+        let mut headers = HeaderMap::new();
+        let future = Utc::now() + ChronoDuration::seconds(60);
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_str(&future.to_rfc2822()).unwrap(),
+        );
+        let parsed = parse_retry_after(&headers).expect("date header parses");
+        assert!(parsed.as_secs() <= 60 && parsed.as_secs() >= 58);
+    }
+
+    #[test]
+    fn parse_retry_after_ignores_missing_header() {
+        assert_eq!(parse_retry_after(&HeaderMap::new()), None);
+    }
+
+    #[tokio::test]
+    async fn wait_backoff_honors_retry_after_over_computed_delay() {
+        let settings = HttpSettings {
+            backoff_strategy: BackoffStrategy::Exponential,
+            max_backoff: Duration::from_secs(10),
+            ..HttpSettings::default()
+        };
+        let client = HttpClient::new(settings, None).expect("client");
+        let mut prev_delay = client.settings.initial_backoff;
+
+        let started = std::time::Instant::now();
+        client
+            .wait_backoff(1, &mut prev_delay, Some(Duration::from_millis(5)))
+            .await;
+        assert!(started.elapsed() >= Duration::from_millis(5));
+    }
+
+    #[tokio::test]
+    async fn wait_backoff_decorrelated_jitter_stays_within_bounds() {
+        let settings = HttpSettings {
+            backoff_strategy: BackoffStrategy::DecorrelatedJitter,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            ..HttpSettings::default()
+        };
+        let client = HttpClient::new(settings, None).expect("client");
+        let mut prev_delay = client.settings.initial_backoff;
+
+        for attempt in 1..=5 {
+            client.wait_backoff(attempt, &mut prev_delay, None).await;
+            assert!(prev_delay >= Duration::from_millis(10));
+            assert!(prev_delay <= Duration::from_millis(50));
+        }
+    }
+
+    #[tokio::test]
+    async fn wait_backoff_full_jitter_never_exceeds_the_exponential_ceiling() {
+        let settings = HttpSettings {
+            backoff_strategy: BackoffStrategy::FullJitter,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            ..HttpSettings::default()
+        };
+        let client = HttpClient::new(settings, None).expect("client");
+        let mut prev_delay = client.settings.initial_backoff;
+
+        for attempt in 1..=5 {
+            client.wait_backoff(attempt, &mut prev_delay, None).await;
+            assert!(prev_delay <= Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn retry_policy_default_retries_429_and_5xx_only() {
+        let policy = RetryPolicy::default();
+        assert!(policy.allows_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(policy.allows_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!policy.allows_status(StatusCode::CONFLICT));
+    }
+
+    #[test]
+    fn retry_policy_can_be_narrowed_to_specific_statuses() {
+        let policy = RetryPolicy {
+            retryable_statuses: vec![409],
+            ..RetryPolicy::default()
+        };
+        assert!(policy.allows_status(StatusCode::CONFLICT));
+        assert!(!policy.allows_status(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    #[test]
+    fn extract_idempotency_key_reads_header_case_insensitively() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Idempotency-Key", HeaderValue::from_static("req-123"));
+        assert_eq!(
+            extract_idempotency_key(&headers),
+            Some("req-123".to_owned())
+        );
+        assert_eq!(extract_idempotency_key(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn replay_idempotent_response_parses_success_and_reconstructs_failure() {
+        #[derive(serde::Deserialize, PartialEq, Debug)]
+        struct Body {
+            ok: bool,
+        }
+
+        let success = IdempotentResponse {
+            status: 200,
+            body: r#"{"ok":true}"#.to_owned(),
+            request_id: Some("req-1".to_owned()),
+        };
+        assert_eq!(
+            replay_idempotent_response::<Body>(success).unwrap(),
+            Body { ok: true }
+        );
+
+        let failure = IdempotentResponse {
+            status: 500,
+            body: "provider down".to_owned(),
+            request_id: None,
+        };
+        let err = replay_idempotent_response::<Body>(failure).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::BdPaymentError::HttpError {
+                status: Some(500),
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn in_memory_idempotency_store_claims_a_key_once_then_replays_the_completed_result() {
+        let store = InMemoryIdempotencyStore::new();
+
+        assert!(matches!(store.begin("key-1").await, IdempotencyClaim::New));
+        assert!(matches!(
+            store.begin("key-1").await,
+            IdempotencyClaim::InFlight
+        ));
+
+        store
+            .complete(
+                "key-1",
+                IdempotentResponse {
+                    status: 201,
+                    body: "{}".to_owned(),
+                    request_id: Some("req-9".to_owned()),
+                },
+            )
+            .await;
+
+        match store.begin("key-1").await {
+            IdempotencyClaim::Completed(response) => assert_eq!(response.status, 201),
+            _ => panic!("expected a completed claim"),
+        }
+    }
+
+    #[tokio::test]
+    async fn http_client_idempotency_precheck_replays_cached_terminal_response() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let client = HttpClient::new(HttpSettings::default(), None)
+            .expect("client")
+            .with_idempotency_store(store.clone());
+
+        assert!(client.idempotency_precheck("order-1").await.is_none());
+
+        store
+            .complete(
+                "order-1",
+                IdempotentResponse {
+                    status: 200,
+                    body: r#"{"status":"ok"}"#.to_owned(),
+                    request_id: None,
+                },
+            )
+            .await;
+
+        let cached = client
+            .idempotency_precheck("order-1")
+            .await
+            .expect("cached response");
+        assert_eq!(cached.status, 200);
+    }
+
+    #[tokio::test]
+    async fn will_retry_with_refreshed_auth_only_applies_to_401_under_bearer_auth() {
+        let with_auth = HttpClient::new(
+            HttpSettings {
+                auth: AuthStrategy::StaticBearer(SecretString::new("tok".to_owned().into())),
+                ..HttpSettings::default()
+            },
+            None,
+        )
+        .expect("client");
+        assert!(with_auth.will_retry_with_refreshed_auth(StatusCode::UNAUTHORIZED));
+        assert!(!with_auth.will_retry_with_refreshed_auth(StatusCode::FORBIDDEN));
+
+        let without_auth = HttpClient::with_default_settings().expect("client");
+        assert!(!without_auth.will_retry_with_refreshed_auth(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn complete_idempotency_skips_caching_a_401_that_a_refresh_retry_will_supersede() {
+        // Regression test: post_form_uncached/request_json_uncached must not call
+        // complete_idempotency for a 401 that post_form/request_json are about to retry with a
+        // refreshed token, or the retried attempt's own idempotency_precheck would immediately
+        // replay this stale 401 instead of actually resending.
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let client = HttpClient::new(
+            HttpSettings {
+                auth: AuthStrategy::StaticBearer(SecretString::new("tok".to_owned().into())),
+                ..HttpSettings::default()
+            },
+            None,
+        )
+        .expect("client")
+        .with_idempotency_store(store.clone());
+
+        assert!(client.idempotency_precheck("order-2").await.is_none());
+
+        if !client.will_retry_with_refreshed_auth(StatusCode::UNAUTHORIZED) {
+            client
+                .complete_idempotency(&Some("order-2".to_owned()), 401, "unauthorized", None)
+                .await;
+        }
+
+        assert!(matches!(
+            store.begin("order-2").await,
+            IdempotencyClaim::InFlight
+        ));
+    }
 }
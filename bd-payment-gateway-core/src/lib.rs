@@ -1,16 +1,25 @@
 pub mod error;
 pub mod http;
 pub mod provider;
+pub mod registry;
 pub mod types;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod webhook;
 
-pub use error::{BdPaymentError, ErrorCode, Result};
+pub use error::{BdPaymentError, ErrorCode, ProviderErrorDetail, ProviderErrorType, Result};
 pub use http::{
-    generate_correlation_id, generate_idempotency_key, HttpClient, HttpLogger, HttpSettings,
+    generate_correlation_id, generate_idempotency_key, redact_text, AuthStrategy, BackoffStrategy,
+    HttpClient, HttpLogger, HttpSettings, IdempotencyClaim, IdempotencyStore, IdempotentResponse,
+    InMemoryIdempotencyStore, RedactionPolicy, TokenManager,
 };
 pub use provider::{
-    InitiatePaymentResponse, PaymentProvider, PaymentStatus, RefundResponse, RefundStatus,
-    VerifyPaymentResponse,
+    constant_time_eq, extract_failure_reason, AuthorizePaymentResponse, CapturePaymentResponse,
+    DynPaymentProvider, FailureReason, InitiatePaymentResponse, PaymentProvider, PaymentStatus,
+    PayoutProvider, PayoutResponse, PayoutStatus, PollConfig, RefundResponse, RefundStatus,
+    VerifyPaymentResponse, VoidPaymentResponse, WebhookEvent, WebhookVerifier,
 };
 pub use types::{
-    Currency, Customer, Environment, Money, OrderId, RedirectUrl, TransactionId, WebhookPayload,
+    Currency, Customer, CustomerBuilder, Environment, LineItem, Money, MoneyBuilder, OrderId,
+    RedirectUrl, TransactionId, WebhookPayload,
 };
@@ -1,9 +1,15 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
+use rand::Rng;
+use reqwest::header::HeaderMap;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tokio::time::sleep;
 use url::Url;
 
-use crate::{BdPaymentError, Currency, Money, Result};
+use crate::{BdPaymentError, Currency, Money, Result, WebhookPayload};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PaymentStatus {
@@ -15,6 +21,31 @@ pub enum PaymentStatus {
     Unknown(String),
 }
 
+impl PaymentStatus {
+    /// Classifies a raw provider status string by keyword, the same substring heuristic each
+    /// gateway's own webhook/IPN handler already applies to normalize its vocabulary (`"VALID"`,
+    /// `"Processing"`, `"Cancelled"`, ...) down to this enum.
+    pub fn from_keyword(raw: &str) -> Self {
+        let lower = raw.to_ascii_lowercase();
+        if (lower.contains("valid") && !lower.contains("invalid"))
+            || lower.contains("success")
+            || lower.contains("paid")
+        {
+            Self::Paid
+        } else if lower.contains("refund") {
+            Self::Refunded
+        } else if lower.contains("pending") {
+            Self::Pending
+        } else if lower.contains("cancel") {
+            Self::Cancelled
+        } else if lower.contains("fail") || lower.contains("invalid") {
+            Self::Failed
+        } else {
+            Self::Unknown(raw.to_owned())
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RefundStatus {
     Pending,
@@ -29,6 +60,12 @@ pub struct InitiatePaymentResponse {
     pub provider_reference: String,
     pub raw: serde_json::Value,
     pub request_id: Option<String>,
+    /// The card network's own reference for this authorization (Visa/Mastercard-style network
+    /// transaction ID), present only for providers that support tokenized recurring charges.
+    /// Persist this alongside the stored payment token; later merchant-initiated charges against
+    /// the same token should carry it forward so reconciliation/dispute tooling can tie the whole
+    /// subscription's charges back to the first authorization.
+    pub network_transaction_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +77,42 @@ pub struct VerifyPaymentResponse {
     pub money: Option<Money>,
     pub raw: serde_json::Value,
     pub request_id: Option<String>,
+    pub failure_reason: Option<FailureReason>,
+    /// See [`InitiatePaymentResponse::network_transaction_id`]; carried through verification so a
+    /// later `charge_recurring`-style call can thread it back to the provider.
+    pub network_transaction_id: Option<String>,
+}
+
+/// Why a `Failed` payment was declined, normalized out of whichever reason/message/error field
+/// the provider's raw response happens to use (see [`extract_failure_reason`]), the same way
+/// hyperswitch's connectors populate a failure reason alongside their terminal-failure status.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureReason {
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Pulls a human-readable decline reason (and, if present, a machine-readable reason code) out of
+/// a provider's raw JSON error payload, trying the field names gateways in this crate are known to
+/// use (`reason`, `message`, `error_reason`, `failure_reason`, `remarks`) before falling back to
+/// `fallback_message`. Intended to be called once a provider has already determined its
+/// [`PaymentStatus`] is [`PaymentStatus::Failed`].
+pub fn extract_failure_reason(raw: &Value, fallback_message: &str) -> FailureReason {
+    let message = raw
+        .get("reason")
+        .or_else(|| raw.get("message"))
+        .or_else(|| raw.get("error_reason"))
+        .or_else(|| raw.get("failure_reason"))
+        .or_else(|| raw.get("remarks"))
+        .and_then(Value::as_str)
+        .unwrap_or(fallback_message)
+        .to_owned();
+    let code = raw
+        .get("error_code")
+        .or_else(|| raw.get("reason_code"))
+        .and_then(Value::as_str)
+        .map(str::to_owned);
+    FailureReason { code, message }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,11 +123,62 @@ pub struct RefundResponse {
     pub request_id: Option<String>,
 }
 
+/// A card gateway's response to [`PaymentProvider::authorize_payment`]: funds are reserved but
+/// not yet moved, so `captured_amount` is typically `None` until a matching
+/// [`PaymentProvider::capture_payment`] call settles some or all of the authorized amount.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizePaymentResponse {
+    pub status: PaymentStatus,
+    pub provider_reference: String,
+    pub captured_amount: Option<Money>,
+    pub raw: serde_json::Value,
+    pub request_id: Option<String>,
+}
+
+/// A card gateway's response to [`PaymentProvider::capture_payment`]; `captured_amount` is the
+/// amount actually settled by this capture, which may be less than the original authorization for
+/// a partial capture.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturePaymentResponse {
+    pub status: PaymentStatus,
+    pub provider_reference: String,
+    pub captured_amount: Option<Money>,
+    pub raw: serde_json::Value,
+    pub request_id: Option<String>,
+}
+
+/// A card gateway's response to [`PaymentProvider::void_payment`], releasing a prior
+/// authorization before it is captured. `captured_amount` is always `None` on a successful void.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoidPaymentResponse {
+    pub status: PaymentStatus,
+    pub provider_reference: String,
+    pub captured_amount: Option<Money>,
+    pub raw: serde_json::Value,
+    pub request_id: Option<String>,
+}
+
+/// A normalized asynchronous payment-status callback (bKash/Nagad/SSLCommerz-style webhook or
+/// IPN), parsed from an inbound POST by [`PaymentProvider::parse_webhook`] after
+/// [`PaymentProvider::verify_webhook`] has authenticated it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub event_type: String,
+    pub provider_reference: String,
+    pub status: PaymentStatus,
+    pub amount: Option<Money>,
+    pub request_id: Option<String>,
+    pub raw: serde_json::Value,
+}
+
 #[async_trait]
 pub trait PaymentProvider: Send + Sync {
     type InitiateRequest: Send + Sync;
     type VerifyRequest: Send + Sync;
     type RefundRequest: Send + Sync;
+    type AuthorizeRequest: Send + Sync;
+    type CaptureRequest: Send + Sync;
+    type VoidRequest: Send + Sync;
 
     async fn initiate_payment(
         &self,
@@ -63,10 +187,446 @@ pub trait PaymentProvider: Send + Sync {
 
     async fn verify_payment(&self, req: &Self::VerifyRequest) -> Result<VerifyPaymentResponse>;
 
+    /// Centralizes a provider's raw status/HTTP-status pair into a [`PaymentStatus`] and, when
+    /// that status is [`PaymentStatus::Failed`], a [`FailureReason`] explaining the decline. The
+    /// default defers to [`PaymentStatus::from_keyword`]'s substring heuristic and a bare
+    /// `FailureReason` carrying the raw status string as its message; a provider with richer
+    /// reason fields in its response body should override this and call
+    /// [`extract_failure_reason`] instead of reimplementing its own ad hoc mapping.
+    fn normalize_status(&self, raw: &str, _http_status: u16) -> (PaymentStatus, Option<FailureReason>) {
+        let status = PaymentStatus::from_keyword(raw);
+        let reason = matches!(status, PaymentStatus::Failed).then(|| FailureReason {
+            code: None,
+            message: raw.to_owned(),
+        });
+        (status, reason)
+    }
+
     async fn refund(&self, _req: &Self::RefundRequest) -> Result<RefundResponse> {
         Err(BdPaymentError::unsupported(
             "This provider does not support refunds through this SDK API.",
             "Use provider dashboard/manual refund flow, or call provider-specific refund API if available.",
         ))
     }
+
+    /// Reserves funds against a card without moving them, for gateways that support the
+    /// authorize/capture split (delayed capture). The default assumes one-shot providers that
+    /// only ever settle through [`Self::initiate_payment`]/[`Self::verify_payment`].
+    async fn authorize_payment(
+        &self,
+        _req: &Self::AuthorizeRequest,
+    ) -> Result<AuthorizePaymentResponse> {
+        Err(BdPaymentError::unsupported(
+            "This provider does not support delayed-capture authorization through this SDK API.",
+            "Use a provider that supports authorize/capture, or call provider-specific authorize API if available.",
+        ))
+    }
+
+    /// Settles some or all of a prior [`Self::authorize_payment`] hold. Implementations should
+    /// support partial capture where the provider allows it.
+    async fn capture_payment(&self, _req: &Self::CaptureRequest) -> Result<CapturePaymentResponse> {
+        Err(BdPaymentError::unsupported(
+            "This provider does not support payment capture through this SDK API.",
+            "Use a provider that supports authorize/capture, or call provider-specific capture API if available.",
+        ))
+    }
+
+    /// Releases a prior [`Self::authorize_payment`] hold before it is captured.
+    async fn void_payment(&self, _req: &Self::VoidRequest) -> Result<VoidPaymentResponse> {
+        Err(BdPaymentError::unsupported(
+            "This provider does not support voiding an authorization through this SDK API.",
+            "Use a provider that supports authorize/capture, or call provider-specific void API if available.",
+        ))
+    }
+
+    /// Authenticates an inbound webhook/IPN POST before its body is trusted. An implementation
+    /// typically recomputes an HMAC-SHA256/SHA512 over `body` under the provider's shared secret
+    /// (see [`crate::webhook::verify_hmac_sha256`]/[`crate::webhook::verify_hmac_sha512`]) and
+    /// constant-time-compares it against a signature header, returning
+    /// `BdPaymentError::validation` on a mismatch or missing signature. The default assumes the
+    /// provider has no webhook channel wired up through this trait yet.
+    fn verify_webhook(&self, _headers: &HeaderMap, _body: &[u8]) -> Result<()> {
+        Err(BdPaymentError::unsupported(
+            "This provider does not support webhook verification through this SDK API.",
+            "Use the provider-specific webhook handler, or implement PaymentProvider::verify_webhook.",
+        ))
+    }
+
+    /// Parses an already-[`Self::verify_webhook`]-ed body into a normalized [`WebhookEvent`]. An
+    /// implementation should thread the callback's own status/reason code through
+    /// [`PaymentStatus::from_keyword`] (or an equivalent explicit mapping) so a failed callback
+    /// reaches callers as `PaymentStatus::Failed` rather than `Unknown`. Does not re-verify the
+    /// signature; call [`Self::verify_webhook`] first.
+    fn parse_webhook(&self, _headers: &HeaderMap, _body: &[u8]) -> Result<WebhookEvent> {
+        Err(BdPaymentError::unsupported(
+            "This provider does not support webhook parsing through this SDK API.",
+            "Use the provider-specific webhook handler, or implement PaymentProvider::parse_webhook.",
+        ))
+    }
+
+    /// Repeatedly calls [`Self::verify_payment`] until it reaches a terminal [`PaymentStatus`]
+    /// (`Paid`/`Failed`/`Cancelled`/`Refunded`) or `config.max_attempts` is exhausted, sleeping
+    /// with truncated exponential backoff (optionally full-jittered) between attempts. A
+    /// `BdPaymentError::HttpError` is retried like a `Pending`/`Unknown` status; any other error
+    /// (validation, config, provider rejection, ...) aborts immediately. Returns
+    /// `BdPaymentError::TimeoutError` once the attempt budget runs out without settling; if the
+    /// final attempt itself fails with an `HttpError`, that error is returned instead.
+    async fn poll_until_settled(
+        &self,
+        req: &Self::VerifyRequest,
+        config: PollConfig,
+    ) -> Result<VerifyPaymentResponse> {
+        let max_attempts = config.max_attempts.max(1);
+        let mut last_status = None;
+
+        for attempt in 0..max_attempts {
+            match self.verify_payment(req).await {
+                Ok(resp) if is_terminal(&resp.status) => return Ok(resp),
+                Ok(resp) => last_status = Some(resp.status),
+                Err(e @ BdPaymentError::HttpError { .. }) => {
+                    if attempt + 1 == max_attempts {
+                        return Err(e);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+
+            sleep(config.delay_for_attempt(attempt)).await;
+        }
+
+        Err(BdPaymentError::timeout(
+            format!(
+                "Payment did not settle after {max_attempts} attempts; last status: {last_status:?}"
+            ),
+            "Increase PollConfig.max_attempts, raise max_delay, or poll again later.",
+            max_attempts,
+        ))
+    }
+}
+
+/// Controls [`PaymentProvider::poll_until_settled`]'s attempt budget and backoff schedule.
+/// `delay_n = min(max_delay, initial_delay * multiplier^n)`, optionally randomized to a uniform
+/// `[0, delay_n]` full jitter when `jitter` is set, to avoid callers' polling loops synchronizing.
+#[derive(Debug, Clone)]
+pub struct PollConfig {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    pub jitter: bool,
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl PollConfig {
+    fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let scaled = self.initial_delay.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        let capped = Duration::from_secs_f64(scaled.min(self.max_delay.as_secs_f64()));
+        if self.jitter {
+            let factor = rand::thread_rng().gen_range(0.0..=1.0);
+            Duration::from_secs_f64(capped.as_secs_f64() * factor)
+        } else {
+            capped
+        }
+    }
+}
+
+fn is_terminal(status: &PaymentStatus) -> bool {
+    matches!(
+        status,
+        PaymentStatus::Paid
+            | PaymentStatus::Failed
+            | PaymentStatus::Cancelled
+            | PaymentStatus::Refunded
+    )
+}
+
+/// Object-safe counterpart of [`PaymentProvider`] for callers that need to store multiple
+/// gateway clients behind one trait object (e.g. a `HashMap<&str, Box<dyn DynPaymentProvider>>`
+/// registry), trading [`PaymentProvider`]'s associated types for `serde_json::Value` at the
+/// boundary.
+#[async_trait]
+pub trait DynPaymentProvider: Send + Sync {
+    async fn initiate(&self, req: Value) -> Result<InitiatePaymentResponse>;
+
+    async fn verify(&self, req: Value) -> Result<VerifyPaymentResponse>;
+
+    async fn refund(&self, req: Value) -> Result<RefundResponse>;
+}
+
+#[async_trait]
+impl<T> DynPaymentProvider for T
+where
+    T: PaymentProvider,
+    T::InitiateRequest: DeserializeOwned,
+    T::VerifyRequest: DeserializeOwned,
+    T::RefundRequest: DeserializeOwned,
+{
+    async fn initiate(&self, req: Value) -> Result<InitiatePaymentResponse> {
+        self.initiate_payment(&deserialize_dyn_request(req)?).await
+    }
+
+    async fn verify(&self, req: Value) -> Result<VerifyPaymentResponse> {
+        self.verify_payment(&deserialize_dyn_request(req)?).await
+    }
+
+    async fn refund(&self, req: Value) -> Result<RefundResponse> {
+        PaymentProvider::refund(self, &deserialize_dyn_request(req)?).await
+    }
+}
+
+/// Authenticates an inbound IPN/webhook POST before its JSON body is trusted as a
+/// [`WebhookPayload`]. [`crate::registry::dispatch_webhook`] normalizes a payload's *contents*
+/// into a [`crate::registry::NormalizedEvent`] but has no opinion on whether the request actually
+/// came from the gateway; a provider that publishes a callback signature scheme implements this
+/// trait so callers can reject forged or replayed callbacks before acting on them.
+pub trait WebhookVerifier: Send + Sync {
+    /// Verifies `raw_body` against `headers` using the provider's signature scheme and, on
+    /// success, returns the parsed [`WebhookPayload`]. Returns `BdPaymentError::validation` on a
+    /// signature mismatch, a missing/malformed signature header, or a timestamp outside the
+    /// implementation's replay-tolerance window.
+    fn verify(&self, raw_body: &[u8], headers: &HeaderMap) -> Result<WebhookPayload>;
+}
+
+/// Terminal/non-terminal states for a [`PayoutProvider::create_payout`]/
+/// [`PayoutProvider::verify_payout`] disbursement, mirroring how [`RefundStatus`] covers a
+/// refund's lifecycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PayoutStatus {
+    Pending,
+    Completed,
+    Failed,
+    Unknown(String),
+}
+
+/// A provider's response to [`PayoutProvider::create_payout`]/[`PayoutProvider::verify_payout`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutResponse {
+    pub status: PayoutStatus,
+    pub provider_reference: String,
+    pub raw: serde_json::Value,
+    pub request_id: Option<String>,
+}
+
+/// Outbound disbursement to a beneficiary (marketplace seller settlement, merchant payout, ...),
+/// the mirror image of [`PaymentProvider`]'s inbound collection flow. Kept as its own trait
+/// rather than folded into [`PaymentProvider`] since not every gateway this crate supports offers
+/// a payout API, and a provider that does can implement just this trait without also providing
+/// the (unrelated) associated types [`PaymentProvider`] requires.
+#[async_trait]
+pub trait PayoutProvider: Send + Sync {
+    type PayoutRequest: Send + Sync;
+    type VerifyPayoutRequest: Send + Sync;
+
+    /// Initiates a disbursement to a beneficiary account/wallet for `req.amount`/`req.currency`,
+    /// tagged with the caller's own `payout_reference` for reconciliation.
+    async fn create_payout(&self, req: &Self::PayoutRequest) -> Result<PayoutResponse>;
+
+    /// Looks up a previously created payout's current [`PayoutStatus`], the payout counterpart of
+    /// [`PaymentProvider::verify_payment`].
+    async fn verify_payout(&self, req: &Self::VerifyPayoutRequest) -> Result<PayoutResponse>;
+}
+
+/// Compares two byte strings in constant time with respect to their *content*, to avoid leaking
+/// how many leading bytes of a signature matched through a timing side channel. Still short-circuits
+/// on a length mismatch, which is not secret.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff: u8 = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+fn deserialize_dyn_request<T: DeserializeOwned>(req: Value) -> Result<T> {
+    serde_json::from_value(req).map_err(|e| {
+        BdPaymentError::validation(
+            format!("Failed to deserialize dynamic provider request: {e}"),
+            "Ensure the JSON payload matches the target provider's request schema.",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    struct ScriptedProvider {
+        statuses: Vec<PaymentStatus>,
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl PaymentProvider for ScriptedProvider {
+        type InitiateRequest = ();
+        type VerifyRequest = ();
+        type RefundRequest = ();
+        type AuthorizeRequest = ();
+        type CaptureRequest = ();
+        type VoidRequest = ();
+
+        async fn initiate_payment(&self, _req: &()) -> Result<InitiatePaymentResponse> {
+            unimplemented!("not exercised by poll_until_settled tests")
+        }
+
+        async fn verify_payment(&self, _req: &()) -> Result<VerifyPaymentResponse> {
+            let i = self.calls.fetch_add(1, Ordering::SeqCst) as usize;
+            let status = self.statuses[i.min(self.statuses.len() - 1)].clone();
+            Ok(VerifyPaymentResponse {
+                status,
+                provider_reference: "REF-1".to_owned(),
+                amount: None,
+                currency: None,
+                money: None,
+                raw: Value::Null,
+                request_id: None,
+                failure_reason: None,
+                network_transaction_id: None,
+            })
+        }
+    }
+
+    fn fast_config(max_attempts: u32) -> PollConfig {
+        PollConfig {
+            max_attempts,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            multiplier: 2.0,
+            jitter: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn poll_until_settled_returns_immediately_on_terminal_status() {
+        let provider = ScriptedProvider {
+            statuses: vec![PaymentStatus::Paid],
+            calls: AtomicU32::new(0),
+        };
+
+        let result = provider
+            .poll_until_settled(&(), fast_config(5))
+            .await
+            .expect("poll");
+
+        assert!(matches!(result.status, PaymentStatus::Paid));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn poll_until_settled_retries_pending_then_settles() {
+        let provider = ScriptedProvider {
+            statuses: vec![
+                PaymentStatus::Pending,
+                PaymentStatus::Unknown("processing".to_owned()),
+                PaymentStatus::Paid,
+            ],
+            calls: AtomicU32::new(0),
+        };
+
+        let result = provider
+            .poll_until_settled(&(), fast_config(5))
+            .await
+            .expect("poll");
+
+        assert!(matches!(result.status, PaymentStatus::Paid));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn poll_until_settled_times_out_while_pending() {
+        let provider = ScriptedProvider {
+            statuses: vec![PaymentStatus::Pending],
+            calls: AtomicU32::new(0),
+        };
+
+        let err = provider
+            .poll_until_settled(&(), fast_config(3))
+            .await
+            .expect_err("pending status should exhaust the attempt budget");
+
+        assert!(matches!(err, BdPaymentError::TimeoutError { attempts: 3, .. }));
+    }
+
+    #[test]
+    fn default_webhook_methods_are_unsupported_until_a_provider_opts_in() {
+        let provider = ScriptedProvider {
+            statuses: vec![PaymentStatus::Paid],
+            calls: AtomicU32::new(0),
+        };
+        let headers = HeaderMap::new();
+
+        assert!(matches!(
+            provider.verify_webhook(&headers, b"{}"),
+            Err(BdPaymentError::Unsupported { .. })
+        ));
+        assert!(matches!(
+            provider.parse_webhook(&headers, b"{}"),
+            Err(BdPaymentError::Unsupported { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn default_authorize_capture_void_methods_are_unsupported_until_a_provider_opts_in() {
+        let provider = ScriptedProvider {
+            statuses: vec![PaymentStatus::Paid],
+            calls: AtomicU32::new(0),
+        };
+
+        assert!(matches!(
+            provider.authorize_payment(&()).await,
+            Err(BdPaymentError::Unsupported { .. })
+        ));
+        assert!(matches!(
+            provider.capture_payment(&()).await,
+            Err(BdPaymentError::Unsupported { .. })
+        ));
+        assert!(matches!(
+            provider.void_payment(&()).await,
+            Err(BdPaymentError::Unsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn default_normalize_status_classifies_failed_raw_text_with_a_failure_reason() {
+        let provider = ScriptedProvider {
+            statuses: vec![PaymentStatus::Paid],
+            calls: AtomicU32::new(0),
+        };
+
+        let (status, reason) = provider.normalize_status("Payment Failed", 200);
+        assert!(matches!(status, PaymentStatus::Failed));
+        assert_eq!(reason.unwrap().message, "Payment Failed");
+
+        let (status, reason) = provider.normalize_status("Paid", 200);
+        assert!(matches!(status, PaymentStatus::Paid));
+        assert!(reason.is_none());
+    }
+
+    #[test]
+    fn extract_failure_reason_prefers_known_fields_over_the_fallback_message() {
+        let raw = serde_json::json!({ "reason": "insufficient_funds", "error_code": "51" });
+        let reason = extract_failure_reason(&raw, "fallback");
+        assert_eq!(reason.message, "insufficient_funds");
+        assert_eq!(reason.code.as_deref(), Some("51"));
+
+        let empty = serde_json::json!({});
+        let reason = extract_failure_reason(&empty, "fallback");
+        assert_eq!(reason.message, "fallback");
+        assert_eq!(reason.code, None);
+    }
 }
@@ -0,0 +1,119 @@
+//! Dynamic, link-time provider registry built on the `inventory` crate, so a web handler can
+//! accept an arbitrary provider's webhook (or build an arbitrary provider's client) by name,
+//! without this crate maintaining a central `match` that every new provider crate must edit.
+//!
+//! Each provider crate submits one [`ProviderRegistration`] for itself via `inventory::submit!`;
+//! this module only looks registrations up by [`ProviderRegistration::name`] and calls through.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::{
+    BdPaymentError, DynPaymentProvider, InitiatePaymentResponse, PaymentStatus, RefundResponse,
+    Result, VerifyPaymentResponse, WebhookPayload,
+};
+
+/// Config JSON handed to a [`ProviderRegistration::build`] hook. Each provider crate knows how to
+/// deserialize its own shape out of this, the same way [`DynPaymentProvider`] deserializes
+/// requests at its boundary.
+pub type ProviderConfigJson = Value;
+
+/// A webhook/IPN event normalized to a small, provider-agnostic shape, independent of which
+/// gateway's callback it came from.
+#[derive(Debug, Clone)]
+pub struct NormalizedEvent {
+    pub provider: &'static str,
+    pub status: PaymentStatus,
+    pub provider_reference: String,
+    pub raw: Value,
+}
+
+/// One provider's registration with the global registry, submitted via `inventory::submit!` from
+/// that provider's own crate at link time.
+pub struct ProviderRegistration {
+    /// Canonical provider name (e.g. `"portwallet"`), matched against [`WebhookPayload::provider`]
+    /// and the name passed to [`build`].
+    pub name: &'static str,
+    pub build: fn(ProviderConfigJson) -> Result<Box<dyn DynPaymentProvider>>,
+    pub parse_webhook: fn(&Value) -> Result<NormalizedEvent>,
+}
+
+inventory::collect!(ProviderRegistration);
+
+fn lookup(name: &str) -> Option<&'static ProviderRegistration> {
+    inventory::iter::<ProviderRegistration>()
+        .into_iter()
+        .find(|reg| reg.name == name)
+}
+
+fn unknown_provider(name: &str) -> BdPaymentError {
+    BdPaymentError::unsupported(
+        format!("No provider is registered under the name {name:?}."),
+        "Check for a typo, or make sure that provider's crate is linked into this binary.",
+    )
+}
+
+/// Builds the concrete provider client registered under `name`, or `Unsupported` if no linked-in
+/// provider crate registered that name.
+pub fn build(name: &str, config: ProviderConfigJson) -> Result<Box<dyn DynPaymentProvider>> {
+    let registration = lookup(name).ok_or_else(|| unknown_provider(name))?;
+    (registration.build)(config)
+}
+
+/// Builds the provider registered under `name` from `config` and immediately calls
+/// [`DynPaymentProvider::initiate`] on it with `req`, so an application routing a payment to one
+/// of N linked-in gateways at runtime (chosen by currency, amount, or merchant config) doesn't
+/// need its own `match` over provider names to get from a name and a JSON request to a response.
+pub async fn initiate(
+    name: &str,
+    config: ProviderConfigJson,
+    req: Value,
+) -> Result<InitiatePaymentResponse> {
+    build(name, config)?.initiate(req).await
+}
+
+/// Like [`initiate`], but for [`DynPaymentProvider::verify`].
+pub async fn verify(
+    name: &str,
+    config: ProviderConfigJson,
+    req: Value,
+) -> Result<VerifyPaymentResponse> {
+    build(name, config)?.verify(req).await
+}
+
+/// Like [`initiate`], but for [`DynPaymentProvider::refund`].
+pub async fn refund(
+    name: &str,
+    config: ProviderConfigJson,
+    req: Value,
+) -> Result<RefundResponse> {
+    build(name, config)?.refund(req).await
+}
+
+/// Looks up `payload.provider` in the registry and normalizes its payload into a
+/// [`NormalizedEvent`], so a single webhook HTTP handler can accept callbacks from any linked-in
+/// provider generically.
+pub fn dispatch_webhook(payload: &WebhookPayload) -> Result<NormalizedEvent> {
+    let registration = lookup(&payload.provider).ok_or_else(|| unknown_provider(&payload.provider))?;
+    (registration.parse_webhook)(&payload.payload)
+}
+
+/// Flattens a JSON object into a `{field: string}` map, the way webhook bodies end up represented
+/// once decoded regardless of whether the gateway posted form-encoded or JSON data. Non-object
+/// payloads (or non-scalar field values) yield an empty/stringified entry rather than an error,
+/// since webhook parsing should degrade to `Unknown` status instead of rejecting the callback.
+pub fn flatten_object(payload: &Value) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    if let Value::Object(map) = payload {
+        for (k, v) in map {
+            let field = match v {
+                Value::String(s) => s.clone(),
+                Value::Null => continue,
+                other => other.to_string(),
+            };
+            fields.insert(k.clone(), field);
+        }
+    }
+    fields
+}
@@ -1,22 +1,54 @@
-use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::{BdPaymentError, Result};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Currency {
     Bdt,
     Usd,
     Eur,
-    Other(String),
+    /// An ISO 4217 code this crate doesn't have a named variant for, carrying its own minor-unit
+    /// exponent since not every such currency uses 2 decimal places (e.g. JPY uses 0, BHD uses
+    /// 3). Construct with [`Self::other`] (defaults to 2) or [`Self::other_with_exponent`] when
+    /// the caller knows the real value.
+    Other(String, u32),
 }
 
+const DEFAULT_OTHER_EXPONENT: u32 = 2;
+
 impl Currency {
+    /// Builds an `Other` currency defaulting to a 2-decimal-place minor unit, the exponent used
+    /// by the large majority of ISO 4217 currencies. Use [`Self::other_with_exponent`] when the
+    /// actual exponent is known to differ.
+    pub fn other(code: impl Into<String>) -> Self {
+        Self::Other(code.into(), DEFAULT_OTHER_EXPONENT)
+    }
+
+    /// Builds an `Other` currency with an explicit minor-unit exponent, for ISO 4217 currencies
+    /// that don't use 2 decimal places (e.g. JPY: 0, BHD: 3).
+    pub fn other_with_exponent(code: impl Into<String>, exponent: u32) -> Self {
+        Self::Other(code.into(), exponent)
+    }
+
     pub fn as_code(&self) -> &str {
         match self {
             Self::Bdt => "BDT",
             Self::Usd => "USD",
             Self::Eur => "EUR",
-            Self::Other(v) => v.as_str(),
+            Self::Other(v, _) => v.as_str(),
+        }
+    }
+
+    /// Number of decimal places this currency's minor unit (poisha/cents) uses, per ISO 4217.
+    /// BDT/USD/EUR all use 2; an `Other` currency carries its own exponent, since not every ISO
+    /// 4217 currency uses 2 decimal places.
+    pub fn minor_unit_exponent(&self) -> u32 {
+        match self {
+            Self::Bdt | Self::Usd | Self::Eur => 2,
+            Self::Other(_, exponent) => *exponent,
         }
     }
 }
@@ -41,7 +73,7 @@ impl<'de> Deserialize<'de> for Currency {
             "BDT" => Self::Bdt,
             "USD" => Self::Usd,
             "EUR" => Self::Eur,
-            _ => Self::Other(upper),
+            _ => Self::other(upper),
         })
     }
 }
@@ -56,6 +88,149 @@ impl Money {
     pub fn new(amount: Decimal, currency: Currency) -> Self {
         Self { amount, currency }
     }
+
+    /// Shorthand for `Money::new(amount, Currency::Bdt)`.
+    pub fn bdt(amount: Decimal) -> Self {
+        Self::new(amount, Currency::Bdt)
+    }
+
+    /// Shorthand for `Money::new(amount, Currency::Usd)`.
+    pub fn usd(amount: Decimal) -> Self {
+        Self::new(amount, Currency::Usd)
+    }
+
+    /// Shorthand for `Money::new(amount, Currency::Eur)`.
+    pub fn eur(amount: Decimal) -> Self {
+        Self::new(amount, Currency::Eur)
+    }
+
+    pub fn builder() -> MoneyBuilder {
+        MoneyBuilder::default()
+    }
+
+    /// Adds two [`Money`] values, requiring matching currencies. Returns
+    /// `BdPaymentError::validation` on a currency mismatch or on `Decimal` overflow.
+    pub fn checked_add(&self, other: &Self) -> Result<Self> {
+        self.checked_op(other, Decimal::checked_add, "add")
+    }
+
+    /// Subtracts `other` from `self`, requiring matching currencies. Returns
+    /// `BdPaymentError::validation` on a currency mismatch or on `Decimal` overflow.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self> {
+        self.checked_op(other, Decimal::checked_sub, "subtract")
+    }
+
+    fn checked_op(
+        &self,
+        other: &Self,
+        op: impl FnOnce(Decimal, Decimal) -> Option<Decimal>,
+        verb: &str,
+    ) -> Result<Self> {
+        if self.currency != other.currency {
+            return Err(BdPaymentError::validation(
+                format!(
+                    "Cannot {verb} Money values with different currencies ({} vs {}).",
+                    self.currency.as_code(),
+                    other.currency.as_code()
+                ),
+                "Convert both values to the same currency before combining them.",
+            ));
+        }
+
+        op(self.amount, other.amount)
+            .map(|amount| Self::new(amount, self.currency.clone()))
+            .ok_or_else(|| {
+                BdPaymentError::validation(
+                    format!("Money {verb} overflowed Decimal's range."),
+                    "Use smaller amounts, or an external arbitrary-precision path.",
+                )
+            })
+    }
+
+    /// Rounds `amount` to `currency`'s [`Currency::minor_unit_exponent`] using banker's rounding
+    /// (round-half-to-even), the standard way to avoid systematic bias when rounding monetary
+    /// totals.
+    pub fn round_to_minor_units(&self) -> Self {
+        Self::new(
+            self.amount
+                .round_dp_with_strategy(
+                    self.currency.minor_unit_exponent(),
+                    RoundingStrategy::MidpointNearestEven,
+                ),
+            self.currency.clone(),
+        )
+    }
+
+    /// Converts to an integer count of the currency's minor units (e.g. poisha/cents), rounding
+    /// to [`Currency::minor_unit_exponent`] first. Returns `BdPaymentError::validation` if the
+    /// rounded amount doesn't fit in an `i64`.
+    pub fn to_minor_units(&self) -> Result<i64> {
+        let exponent = self.currency.minor_unit_exponent();
+        let rounded = self.round_to_minor_units().amount;
+        let scaled = 10i64
+            .checked_pow(exponent)
+            .and_then(|factor| rounded.checked_mul(Decimal::from(factor)));
+        scaled.and_then(|s| s.to_i64()).ok_or_else(|| {
+            BdPaymentError::validation(
+                format!("Money amount {rounded} does not fit in an i64 minor-unit count."),
+                "Use a smaller amount or handle this currency out-of-band.",
+            )
+        })
+    }
+
+    /// Builds a [`Money`] from an integer count of minor units (e.g. poisha/cents), the format
+    /// several gateways transmit amounts in.
+    pub fn from_minor_units(minor_units: i64, currency: Currency) -> Self {
+        let exponent = currency.minor_unit_exponent();
+        Self::new(Decimal::new(minor_units, exponent), currency)
+    }
+}
+
+/// Fluent builder for [`Money`]. `build()` requires both `amount` and `currency` to have been
+/// set, so [`Money::new`]/[`Money::bdt`] remain the terser option when both are known up front.
+#[derive(Debug, Clone, Default)]
+pub struct MoneyBuilder {
+    amount: Option<Decimal>,
+    currency: Option<Currency>,
+}
+
+impl MoneyBuilder {
+    pub fn amount(mut self, amount: Decimal) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    pub fn build(self) -> Result<Money> {
+        let amount = self.amount.ok_or_else(|| {
+            BdPaymentError::validation(
+                "amount is required to build a Money value.",
+                "Call .amount(..) before .build().",
+            )
+        })?;
+        let currency = self.currency.ok_or_else(|| {
+            BdPaymentError::validation(
+                "currency is required to build a Money value.",
+                "Call .currency(..) before .build().",
+            )
+        })?;
+        Ok(Money::new(amount, currency))
+    }
+}
+
+/// One line of an itemized invoice. Providers that accept a cart/product breakdown alongside a
+/// flat total (instead of just the total) serialize a `Vec<LineItem>` into their own
+/// provider-specific request body shape.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LineItem {
+    pub name: String,
+    pub quantity: u32,
+    pub unit_price: Money,
+    pub sku: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -69,6 +244,61 @@ pub struct Customer {
     pub country: Option<String>,
 }
 
+impl Customer {
+    pub fn builder() -> CustomerBuilder {
+        CustomerBuilder::default()
+    }
+}
+
+/// Fluent builder for [`Customer`]. Every field is optional, so `build()` can't fail; it's here
+/// purely for ergonomic chained construction alongside [`Money::builder`] and the provider
+/// request builders.
+#[derive(Debug, Clone, Default)]
+pub struct CustomerBuilder {
+    inner: Customer,
+}
+
+impl CustomerBuilder {
+    pub fn name(mut self, v: impl Into<String>) -> Self {
+        self.inner.name = Some(v.into());
+        self
+    }
+
+    pub fn email(mut self, v: impl Into<String>) -> Self {
+        self.inner.email = Some(v.into());
+        self
+    }
+
+    pub fn phone(mut self, v: impl Into<String>) -> Self {
+        self.inner.phone = Some(v.into());
+        self
+    }
+
+    pub fn address(mut self, v: impl Into<String>) -> Self {
+        self.inner.address = Some(v.into());
+        self
+    }
+
+    pub fn city(mut self, v: impl Into<String>) -> Self {
+        self.inner.city = Some(v.into());
+        self
+    }
+
+    pub fn postcode(mut self, v: impl Into<String>) -> Self {
+        self.inner.postcode = Some(v.into());
+        self
+    }
+
+    pub fn country(mut self, v: impl Into<String>) -> Self {
+        self.inner.country = Some(v.into());
+        self
+    }
+
+    pub fn build(self) -> Customer {
+        self.inner
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(transparent)]
 pub struct OrderId(pub String);
@@ -94,6 +324,31 @@ pub enum Environment {
     CustomBaseUrl(Url),
 }
 
+impl<'de> Deserialize<'de> for Environment {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Mode(String),
+            Custom { custom_base_url: Url },
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Mode(mode) => match mode.to_ascii_lowercase().as_str() {
+                "sandbox" => Ok(Environment::Sandbox),
+                "production" | "live" => Ok(Environment::Production),
+                other => Err(serde::de::Error::custom(format!(
+                    "environment must be \"sandbox\", \"production\", or {{\"custom_base_url\": ...}}, got {other:?}"
+                ))),
+            },
+            Raw::Custom { custom_base_url } => Ok(Environment::CustomBaseUrl(custom_base_url)),
+        }
+    }
+}
+
 impl Environment {
     pub fn resolve(&self, sandbox_base: &str, production_base: &str) -> crate::Result<Url> {
         match self {
@@ -0,0 +1,522 @@
+//! Loads third-party payment provider adapters compiled to WebAssembly instead of linked-in Rust
+//! crates (`bd-payment-gateway-shurjopay`, `-sslcommerz`, ...), so an operator can add a new
+//! Bangladeshi gateway without recompiling this crate. A guest module never gets a raw socket —
+//! it calls the imported `http_request` function, which this host implements on top of
+//! [`HttpClient`], so a plugin's outbound calls get the same retries, backoff, logging, and
+//! `redact_headers`/`redact_json` handling as a built-in provider's.
+//!
+//! Host and guest exchange MessagePack-encoded buffers keyed by operation name, a waPC-style RPC
+//! boundary: the guest exports `create_payment`/`verify_payment`/`refund` (matching
+//! [`DynPaymentProvider`]'s `Value`-in/`Value`-out shape, since a WASM guest has no way to speak
+//! this crate's associated-type [`PaymentProvider`] trait) plus an `alloc(size) -> ptr` helper the
+//! host uses to copy request bytes into guest memory; each entry point returns a packed
+//! `(ptr << 32) | len` pointing at its MessagePack-encoded response. The host in turn imports
+//! `http_request`, `now`, and `random` into the guest under the `env` module.
+//!
+//! Feature-gated behind `wasm`, since `wasmtime` is a heavy, platform-specific dependency that
+//! most consumers of this crate — who only need the built-in providers — shouldn't have to pull
+//! in.
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand::RngCore;
+use reqwest::Method;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use wasmtime::{
+    Caller, Config, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TypedFunc,
+};
+
+use crate::http::HttpClient;
+use crate::{BdPaymentError, Result};
+
+/// The three entry points a guest module must export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuestOperation {
+    CreatePayment,
+    VerifyPayment,
+    Refund,
+}
+
+impl GuestOperation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatePayment => "create_payment",
+            Self::VerifyPayment => "verify_payment",
+            Self::Refund => "refund",
+        }
+    }
+}
+
+/// Returned when a name doesn't match one of [`GuestOperation`]'s variants. Kept distinct from
+/// [`BdPaymentError`] so dispatch code can match on it directly. [`WasmProvider::call`] takes an
+/// already-parsed [`GuestOperation`], so this error is only seen by code that parses a name itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownOperation(pub String);
+
+impl std::str::FromStr for GuestOperation {
+    type Err = UnknownOperation;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "create_payment" => Ok(Self::CreatePayment),
+            "verify_payment" => Ok(Self::VerifyPayment),
+            "refund" => Ok(Self::Refund),
+            other => Err(UnknownOperation(other.to_owned())),
+        }
+    }
+}
+
+/// A suggested value for [`WasmProviderConfig::max_memory_bytes`]: a generous ceiling for a
+/// JSON-in/JSON-out payment adapter that still keeps a misbehaving guest from exhausting host
+/// memory. Nothing applies it automatically; the limit used is always the config's own value.
+pub const DEFAULT_MAX_GUEST_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Config for loading one guest module. `timeout` bounds total wall-clock guest execution time
+/// (not just its outbound HTTP calls) via wasmtime's epoch interruption, so a misbehaving or
+/// malicious guest can't hang the host thread. `max_memory_bytes` bounds its linear memory growth
+/// the same way, via wasmtime's `StoreLimits`, so a guest can't OOM the host instead.
+#[derive(Clone)]
+pub struct WasmProviderConfig {
+    pub module_name: String,
+    pub wasm_bytes: Arc<[u8]>,
+    pub timeout: Duration,
+    pub max_memory_bytes: usize,
+}
+
+/// The wire shape of the `http_request` host call: `(method, url, headers, body)` in,
+/// `(status, headers, body)` out. Bodies travel as raw bytes, but the host sends the call through
+/// `HttpClient::request_json`: a request body is forwarded only if it parses as JSON (otherwise
+/// it is dropped), the response must be JSON, and a successful response is reported as status
+/// 200 with no headers.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GuestHttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GuestHttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Context shared with a running guest via the imported host functions. `http` is the same
+/// [`HttpClient`] instance a linked-in provider crate would use, so a plugin's outbound calls are
+/// retried, backed off, and redacted in logs identically to a built-in gateway's. `limits` bounds
+/// the guest's linear memory growth via [`Store::limiter`], so a malicious or buggy module
+/// can't OOM the host.
+struct HostContext {
+    module_name: String,
+    http: HttpClient,
+    memory: Option<Memory>,
+    alloc: Option<TypedFunc<u32, u32>>,
+    limits: StoreLimits,
+}
+
+/// One loaded WASM provider adapter. Compiles `wasm_bytes` once in [`Self::load`] and
+/// re-instantiates a fresh [`Store`] per call, so concurrent calls into the same module never
+/// share mutable guest state.
+pub struct WasmProvider {
+    engine: Engine,
+    module: Module,
+    module_name: String,
+    http: HttpClient,
+    timeout: Duration,
+    max_memory_bytes: usize,
+}
+
+impl WasmProvider {
+    /// Compiles `config.wasm_bytes` ahead of time so [`Self::call`] only pays instantiation cost,
+    /// not compilation, on the hot path.
+    pub fn load(config: WasmProviderConfig, http: HttpClient) -> Result<Self> {
+        let mut engine_config = Config::new();
+        engine_config.epoch_interruption(true);
+        engine_config.async_support(true);
+
+        let engine = Engine::new(&engine_config).map_err(|e| {
+            BdPaymentError::plugin(
+                &config.module_name,
+                "load",
+                format!("Failed to initialize the WASM engine: {e}"),
+                "Check that wasmtime is available for this platform/target.",
+            )
+        })?;
+
+        let module = Module::new(&engine, &config.wasm_bytes[..]).map_err(|e| {
+            BdPaymentError::plugin(
+                &config.module_name,
+                "load",
+                format!("Failed to compile the WASM module: {e}"),
+                "Confirm the module is a valid wasm32 binary built against this host's ABI.",
+            )
+        })?;
+
+        Ok(Self {
+            engine,
+            module,
+            module_name: config.module_name,
+            http,
+            timeout: config.timeout,
+            max_memory_bytes: config.max_memory_bytes,
+        })
+    }
+
+    /// Invokes one guest entry point: serializes `request` to MessagePack, copies it into a fresh
+    /// guest instance's memory, calls the export named after `operation`, and deserializes the
+    /// MessagePack bytes it returns as `R`. A background task bumps the engine's epoch after
+    /// `self.timeout` elapses, so a guest that runs long is interrupted with a trap instead of
+    /// hanging the host indefinitely.
+    pub async fn call<T, R>(&self, operation: GuestOperation, request: &T) -> Result<R>
+    where
+        T: Serialize + Sync,
+        R: DeserializeOwned,
+    {
+        let request_bytes = rmp_serde::to_vec_named(request).map_err(|e| {
+            BdPaymentError::plugin(
+                &self.module_name,
+                operation.as_str(),
+                format!("Failed to encode request as MessagePack: {e}"),
+                "Ensure the request type only uses MessagePack-representable fields.",
+            )
+        })?;
+
+        let response_bytes = self.invoke_guest(operation, request_bytes).await?;
+
+        rmp_serde::from_slice(&response_bytes).map_err(|e| {
+            BdPaymentError::plugin(
+                &self.module_name,
+                operation.as_str(),
+                format!("Failed to decode the guest's MessagePack response: {e}"),
+                "Confirm the guest's return value matches this host's expected response shape.",
+            )
+        })
+    }
+
+    async fn invoke_guest(&self, operation: GuestOperation, request_bytes: Vec<u8>) -> Result<Vec<u8>> {
+        let plugin_err = |op: &str, detail: String, hint: &str| {
+            BdPaymentError::plugin(&self.module_name, op, detail, hint)
+        };
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.max_memory_bytes)
+            .build();
+        let mut store = Store::new(
+            &self.engine,
+            HostContext {
+                module_name: self.module_name.clone(),
+                http: self.http.clone(),
+                memory: None,
+                alloc: None,
+                limits,
+            },
+        );
+        store.set_epoch_deadline(1);
+        store.limiter(|ctx| &mut ctx.limits);
+
+        let engine = self.engine.clone();
+        let timeout = self.timeout;
+        let deadline_guard = tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            engine.increment_epoch();
+        });
+
+        let mut linker: Linker<HostContext> = Linker::new(&self.engine);
+        register_host_functions(&mut linker).map_err(|e| {
+            plugin_err(
+                operation.as_str(),
+                format!("Failed to register host imports: {e}"),
+                "This indicates a host bug in this crate, not the guest module.",
+            )
+        })?;
+
+        let instance = linker
+            .instantiate_async(&mut store, &self.module)
+            .await
+            .map_err(|e| {
+                plugin_err(
+                    operation.as_str(),
+                    format!("Failed to instantiate the guest module: {e}"),
+                    "Confirm the module only imports the host functions this crate provides \
+                     (http_request, now, random) under the `env` module.",
+                )
+            })?;
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| {
+                plugin_err(
+                    operation.as_str(),
+                    "Guest module does not export linear memory named \"memory\".".to_owned(),
+                    "Export the standard `memory` item from the guest module.",
+                )
+            })?;
+        let alloc = instance
+            .get_typed_func::<u32, u32>(&mut store, "alloc")
+            .map_err(|e| {
+                plugin_err(
+                    operation.as_str(),
+                    format!("Guest module does not export alloc(size: u32) -> u32: {e}"),
+                    "Export an `alloc` function the host can use to copy request bytes in.",
+                )
+            })?;
+        store.data_mut().memory = Some(memory);
+        store.data_mut().alloc = Some(alloc);
+
+        let entry = instance
+            .get_typed_func::<(u32, u32), u64>(&mut store, operation.as_str())
+            .map_err(|e| {
+                plugin_err(
+                    operation.as_str(),
+                    format!("Guest module does not export {:?}: {e}", operation.as_str()),
+                    "Implement this entry point, or avoid dispatching it to this module.",
+                )
+            })?;
+
+        let in_ptr = alloc
+            .call_async(&mut store, request_bytes.len() as u32)
+            .await
+            .map_err(|e| {
+                plugin_err(
+                    operation.as_str(),
+                    format!("Guest alloc trapped: {e}"),
+                    "Check the guest module's alloc implementation.",
+                )
+            })?;
+        memory
+            .write(&mut store, in_ptr as usize, &request_bytes)
+            .map_err(|e| {
+                plugin_err(
+                    operation.as_str(),
+                    format!("Failed to write request bytes into guest memory: {e}"),
+                    "Ensure alloc() returned enough space for the request payload.",
+                )
+            })?;
+
+        let packed = entry
+            .call_async(&mut store, (in_ptr, request_bytes.len() as u32))
+            .await;
+        deadline_guard.abort();
+        let packed = packed.map_err(|e| {
+            plugin_err(
+                operation.as_str(),
+                format!("Guest call failed or trapped: {e}"),
+                "Check the guest module's logs, or whether it hit the configured timeout.",
+            )
+        })?;
+
+        let (out_ptr, out_len) = unpack(packed);
+        let guest_memory_len = memory.data_size(&store) as u64;
+        if guest_range_out_of_bounds(out_ptr, out_len, guest_memory_len) {
+            return Err(plugin_err(
+                operation.as_str(),
+                format!(
+                    "Guest returned an out-of-bounds response (ptr={out_ptr}, len={out_len}) \
+                     against {guest_memory_len} bytes of guest memory.",
+                ),
+                "Confirm the guest's entry point returns a (ptr, len) pointing at a response it \
+                 actually wrote in its own linear memory.",
+            ));
+        }
+        let mut response_bytes = vec![0u8; out_len as usize];
+        memory
+            .read(&store, out_ptr as usize, &mut response_bytes)
+            .map_err(|e| {
+                plugin_err(
+                    operation.as_str(),
+                    format!("Failed to read guest response bytes: {e}"),
+                    "Confirm the returned (ptr, len) points at memory the guest actually wrote.",
+                )
+            })?;
+
+        Ok(response_bytes)
+    }
+}
+
+fn pack(ptr: u32, len: u32) -> u64 {
+    ((ptr as u64) << 32) | (len as u64)
+}
+
+fn unpack(packed: u64) -> (u32, u32) {
+    ((packed >> 32) as u32, packed as u32)
+}
+
+/// Whether a guest-reported `(ptr, len)` range — a response the guest claims to have written,
+/// or a request it asks the host to read — would reach past the end of its own linear memory
+/// (`memory_len` bytes), so the host can reject it before trusting `len` to size an allocation —
+/// a fully guest-controlled `len` up to `u32::MAX` must never drive a `Vec` allocation unchecked.
+fn guest_range_out_of_bounds(ptr: u32, len: u32, memory_len: u64) -> bool {
+    (ptr as u64).saturating_add(len as u64) > memory_len
+}
+
+fn register_host_functions(linker: &mut Linker<HostContext>) -> anyhow::Result<()> {
+    linker.func_wrap_async(
+        "env",
+        "http_request",
+        |mut caller: Caller<'_, HostContext>, (ptr, len): (u32, u32)| {
+            Box::new(async move { host_http_request(&mut caller, ptr, len).await })
+        },
+    )?;
+
+    linker.func_wrap("env", "now", |_caller: Caller<'_, HostContext>| -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+    })?;
+
+    linker.func_wrap("env", "random", |_caller: Caller<'_, HostContext>| -> u64 {
+        rand::thread_rng().next_u64()
+    })?;
+
+    Ok(())
+}
+
+async fn host_http_request(
+    caller: &mut Caller<'_, HostContext>,
+    ptr: u32,
+    len: u32,
+) -> anyhow::Result<u64> {
+    let memory = caller
+        .data()
+        .memory
+        .ok_or_else(|| anyhow::anyhow!("guest memory not initialized before host call"))?;
+
+    let guest_memory_len = memory.data_size(&caller) as u64;
+    if guest_range_out_of_bounds(ptr, len, guest_memory_len) {
+        return Err(anyhow::anyhow!(
+            "guest passed an out-of-bounds http_request buffer (ptr={ptr}, len={len}) \
+             against {guest_memory_len} bytes of guest memory",
+        ));
+    }
+
+    let mut request_bytes = vec![0u8; len as usize];
+    memory.read(&caller, ptr as usize, &mut request_bytes)?;
+    let request: GuestHttpRequest = rmp_serde::from_slice(&request_bytes)?;
+
+    let method = Method::from_bytes(request.method.as_bytes())
+        .map_err(|e| anyhow::anyhow!("invalid HTTP method {:?}: {e}", request.method))?;
+    let url = url::Url::parse(&request.url)
+        .map_err(|e| anyhow::anyhow!("invalid URL {:?}: {e}", request.url))?;
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    for (name, value) in &request.headers {
+        headers.insert(
+            reqwest::header::HeaderName::from_bytes(name.as_bytes())?,
+            reqwest::header::HeaderValue::from_str(value)?,
+        );
+    }
+
+    let body_value = request
+        .body
+        .as_ref()
+        .map(|b| serde_json::from_slice::<serde_json::Value>(b))
+        .transpose()
+        .unwrap_or(None);
+
+    let module_name = caller.data().module_name.clone();
+    let http = caller.data().http.clone();
+    let response = http
+        .request_json::<serde_json::Value, serde_json::Value>(
+            method,
+            &url,
+            headers,
+            body_value.as_ref(),
+        )
+        .await;
+
+    let guest_response = match response {
+        Ok(value) => GuestHttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: serde_json::to_vec(&value)?,
+        },
+        // A provider HTTP error still reaches the guest as a response (status + body) rather
+        // than a host trap, since a plugin should see and react to a 4xx/5xx the same way a
+        // linked-in provider crate would via `HttpClient`.
+        Err(BdPaymentError::HttpError { status, body, .. }) => GuestHttpResponse {
+            status: status.unwrap_or(0),
+            headers: Vec::new(),
+            body: body.unwrap_or_default().into_bytes(),
+        },
+        Err(e) => {
+            return Err(anyhow::anyhow!(
+                "http_request on behalf of plugin {module_name:?} failed before a response was \
+                 received: {e}"
+            ));
+        }
+    };
+
+    write_response(caller, guest_response).await
+}
+
+async fn write_response(
+    caller: &mut Caller<'_, HostContext>,
+    response: GuestHttpResponse,
+) -> anyhow::Result<u64> {
+    let memory = caller
+        .data()
+        .memory
+        .ok_or_else(|| anyhow::anyhow!("guest memory not initialized before host call"))?;
+    let alloc = caller
+        .data()
+        .alloc
+        .ok_or_else(|| anyhow::anyhow!("guest alloc not initialized before host call"))?;
+
+    let encoded = rmp_serde::to_vec_named(&response)?;
+    let out_ptr = alloc.call_async(&mut *caller, encoded.len() as u32).await?;
+    memory.write(&mut *caller, out_ptr as usize, &encoded)?;
+
+    Ok(pack(out_ptr, encoded.len() as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guest_operation_round_trips_known_names() {
+        for op in [
+            GuestOperation::CreatePayment,
+            GuestOperation::VerifyPayment,
+            GuestOperation::Refund,
+        ] {
+            assert_eq!(op.as_str().parse::<GuestOperation>(), Ok(op));
+        }
+    }
+
+    #[test]
+    fn guest_operation_rejects_unknown_names() {
+        assert_eq!(
+            "settle_now".parse::<GuestOperation>(),
+            Err(UnknownOperation("settle_now".to_owned()))
+        );
+    }
+
+    #[test]
+    fn pack_unpack_round_trips() {
+        assert_eq!(unpack(pack(123, 456)), (123, 456));
+        assert_eq!(unpack(pack(u32::MAX, u32::MAX)), (u32::MAX, u32::MAX));
+    }
+
+    #[test]
+    fn guest_range_out_of_bounds_accepts_a_range_within_guest_memory() {
+        assert!(!guest_range_out_of_bounds(0, 64, 1024));
+        assert!(!guest_range_out_of_bounds(960, 64, 1024));
+    }
+
+    #[test]
+    fn guest_range_out_of_bounds_rejects_a_length_past_the_end_of_guest_memory() {
+        assert!(guest_range_out_of_bounds(961, 64, 1024));
+        assert!(guest_range_out_of_bounds(0, u32::MAX, 1024));
+    }
+
+    #[test]
+    fn guest_range_out_of_bounds_does_not_overflow_on_a_maximal_ptr_and_len() {
+        assert!(guest_range_out_of_bounds(u32::MAX, u32::MAX, 1024));
+    }
+}
@@ -0,0 +1,352 @@
+//! Generic cryptographic primitives for authenticating inbound webhook/IPN callbacks. The crate
+//! only ever speaks outbound HTTP through [`crate::http::HttpClient`]; this module is the other
+//! half, verifying that a POST a merchant's server received actually came from the gateway before
+//! its JSON body is trusted as a [`crate::WebhookPayload`].
+//!
+//! Provider verification code (e.g. SSLCOMMERZ's sorted-field MD5 IPN check) calls into these
+//! free functions rather than hand-rolling its own digest/comparison, so those checks go through
+//! the same constant-time comparison and the same field redaction when logging a verification
+//! attempt. PortWallet's header signature is checked in its own crate with [`constant_time_eq`]
+//! directly and is not logged through this module.
+
+use std::collections::{BTreeMap, HashMap};
+
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Sha256, Sha512};
+use tracing::{debug, warn};
+
+use crate::http::is_sensitive_key;
+use crate::provider::constant_time_eq;
+use crate::{BdPaymentError, Result};
+
+/// Verifies `signature_hex` (lowercase hex) is the HMAC-SHA256 of `payload` under `secret`.
+pub fn verify_hmac_sha256(payload: &[u8], signature_hex: &str, secret: &SecretString) -> Result<()> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.expose_secret().as_bytes())
+        .expect("HMAC accepts a key of any length");
+    mac.update(payload);
+    finish_hmac_verification(&mac.finalize().into_bytes(), signature_hex, "HMAC-SHA256")
+}
+
+/// Verifies `signature_hex` (lowercase hex) is the HMAC-SHA512 of `payload` under `secret`.
+pub fn verify_hmac_sha512(payload: &[u8], signature_hex: &str, secret: &SecretString) -> Result<()> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(secret.expose_secret().as_bytes())
+        .expect("HMAC accepts a key of any length");
+    mac.update(payload);
+    finish_hmac_verification(&mac.finalize().into_bytes(), signature_hex, "HMAC-SHA512")
+}
+
+/// Verifies `signature_hex` (lowercase hex, 64 raw bytes) is a valid Ed25519 signature over
+/// `payload` under `public_key_hex` (lowercase hex, 32 raw bytes), the scheme a handful of
+/// newer regional gateways use instead of an HMAC over a shared secret.
+pub fn verify_ed25519(payload: &[u8], signature_hex: &str, public_key_hex: &str) -> Result<()> {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    let signature_bytes = decode_hex(signature_hex, "Webhook Ed25519 signature")?;
+    let signature_bytes: [u8; 64] = signature_bytes.try_into().map_err(|v: Vec<u8>| {
+        BdPaymentError::validation(
+            format!("Webhook Ed25519 signature must be 64 bytes, got {}.", v.len()),
+            "Ensure the signature header carries a raw 64-byte Ed25519 signature, hex-encoded.",
+        )
+    })?;
+
+    let public_key_bytes = decode_hex(public_key_hex, "Webhook Ed25519 public key")?;
+    let public_key_bytes: [u8; 32] = public_key_bytes.try_into().map_err(|v: Vec<u8>| {
+        BdPaymentError::validation(
+            format!("Webhook Ed25519 public key must be 32 bytes, got {}.", v.len()),
+            "Provide the provider's raw 32-byte Ed25519 public key, hex-encoded.",
+        )
+    })?;
+
+    let verifying_key = VerifyingKey::from_bytes(&public_key_bytes).map_err(|e| {
+        BdPaymentError::validation(
+            format!("Invalid Ed25519 public key: {e}"),
+            "Confirm the configured public key matches the provider's published key.",
+        )
+    })?;
+    let signature = Signature::from_bytes(&signature_bytes);
+
+    match verifying_key.verify(payload, &signature) {
+        Ok(()) => {
+            debug!(scheme = "Ed25519", "webhook signature verified");
+            Ok(())
+        }
+        Err(_) => {
+            warn!(scheme = "Ed25519", "webhook signature verification failed");
+            Err(BdPaymentError::validation(
+                "Webhook Ed25519 signature does not match the payload.",
+                "Reject this callback; it did not originate from the holder of the configured key.",
+            ))
+        }
+    }
+}
+
+/// Authenticates an IPN whose signature scheme concatenates a sorted, `&`-joined `key=value` list
+/// of fields — named by `verify_key_field` — and HMAC-SHA256s them under `secret`, comparing the
+/// result against the hex signature named by `signature_field`. For a gateway that names its
+/// fields `verify_key`/`verify_sign` (SSLCOMMERZ's naming convention) but signs with MD5 mixed
+/// into the sort instead of an HMAC, see [`verify_sorted_field_md5`] — SSLCOMMERZ itself uses that
+/// scheme, not this one.
+pub fn verify_sorted_field_hmac_sha256(
+    fields: &HashMap<String, String>,
+    verify_key_field: &str,
+    signature_field: &str,
+    secret: &SecretString,
+) -> Result<()> {
+    let verify_key = fields.get(verify_key_field).ok_or_else(|| {
+        BdPaymentError::validation(
+            format!("Webhook payload is missing the {verify_key_field:?} field."),
+            "Ensure the IPN POST body was forwarded unmodified.",
+        )
+    })?;
+    let signature_hex = fields.get(signature_field).ok_or_else(|| {
+        BdPaymentError::validation(
+            format!("Webhook payload is missing the {signature_field:?} field."),
+            "Ensure the IPN POST body was forwarded unmodified.",
+        )
+    })?;
+
+    let mut keys: Vec<&str> = verify_key
+        .split(',')
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .collect();
+    keys.sort_unstable();
+
+    let mut hash_data = String::new();
+    for key in &keys {
+        if let Some(value) = fields.get(*key) {
+            hash_data.push_str(key);
+            hash_data.push('=');
+            hash_data.push_str(value);
+            hash_data.push('&');
+        }
+    }
+    hash_data.pop();
+
+    log_verification_attempt("sorted-field HMAC-SHA256", fields);
+    verify_hmac_sha256(hash_data.as_bytes(), signature_hex, secret)
+}
+
+/// Authenticates SSLCOMMERZ's IPN scheme: each `key=value` pair named by `verify_key_field`
+/// (SSLCOMMERZ's `verify_key`), plus `store_passwd=<md5(secret)>`, sorted alphabetically as whole
+/// `key=value` strings (not by key alone — `store_passwd`'s sorted position depends on the other
+/// field names present) and `&`-joined, MD5-hashed and compared in constant time, case-insensitively,
+/// against the hex signature named by `signature_field` (SSLCOMMERZ's `verify_sign`).
+pub fn verify_sorted_field_md5(
+    fields: &HashMap<String, String>,
+    verify_key_field: &str,
+    signature_field: &str,
+    secret: &SecretString,
+) -> Result<()> {
+    let verify_key = fields.get(verify_key_field).ok_or_else(|| {
+        BdPaymentError::validation(
+            format!("Webhook payload is missing the {verify_key_field:?} field."),
+            "Ensure the IPN POST body was forwarded unmodified.",
+        )
+    })?;
+    let provided = fields.get(signature_field).ok_or_else(|| {
+        BdPaymentError::validation(
+            format!("Webhook payload is missing the {signature_field:?} field."),
+            "Ensure the IPN POST body was forwarded unmodified.",
+        )
+    })?;
+
+    let keys = verify_key.split(',').map(str::trim).filter(|k| !k.is_empty());
+    let mut pairs: Vec<String> = keys
+        .filter_map(|key| fields.get(key).map(|value| format!("{key}={value}")))
+        .collect();
+    pairs.push(format!(
+        "store_passwd={:x}",
+        md5::compute(secret.expose_secret())
+    ));
+    pairs.sort_unstable();
+
+    let hash_data = pairs.join("&");
+    let computed = format!("{:x}", md5::compute(hash_data));
+
+    log_verification_attempt("sorted-field MD5", fields);
+    if constant_time_eq(
+        computed.to_ascii_lowercase().as_bytes(),
+        provided.to_ascii_lowercase().as_bytes(),
+    ) {
+        debug!(scheme = "sorted-field MD5", "webhook signature verified");
+        Ok(())
+    } else {
+        warn!(scheme = "sorted-field MD5", "webhook signature verification failed");
+        Err(BdPaymentError::validation(
+            "Webhook sorted-field MD5 signature does not match the computed digest.",
+            "Reject this callback; it did not originate from the holder of the configured secret.",
+        ))
+    }
+}
+
+fn finish_hmac_verification(computed: &[u8], signature_hex: &str, scheme: &str) -> Result<()> {
+    let provided = decode_hex(signature_hex, &format!("Webhook {scheme} signature"))?;
+
+    if constant_time_eq(computed, &provided) {
+        debug!(scheme, "webhook signature verified");
+        Ok(())
+    } else {
+        warn!(scheme, "webhook signature verification failed");
+        Err(BdPaymentError::validation(
+            format!("Webhook {scheme} signature does not match the computed digest."),
+            "Reject this callback; it did not originate from the holder of the configured secret.",
+        ))
+    }
+}
+
+fn decode_hex(hex: &str, context: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return Err(BdPaymentError::validation(
+            format!("{context} has an odd number of hex characters."),
+            "Ensure the signature/key header value was forwarded unmodified.",
+        ));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<std::result::Result<Vec<u8>, _>>()
+        .map_err(|e| {
+            BdPaymentError::validation(
+                format!("{context} is not valid hex: {e}"),
+                "Ensure the signature/key header value was forwarded unmodified.",
+            )
+        })
+}
+
+/// Logs which fields went into a signature recomputation without leaking secret-shaped values
+/// (`token`, `signature`, ...), reusing the same [`is_sensitive_key`] heuristic
+/// [`crate::http::redact_json`] applies to outbound request/response logging.
+fn log_verification_attempt(scheme: &str, fields: &HashMap<String, String>) {
+    let redacted: BTreeMap<&str, &str> = fields
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.as_str(),
+                if is_sensitive_key(k) {
+                    "[REDACTED]"
+                } else {
+                    v.as_str()
+                },
+            )
+        })
+        .collect();
+    debug!(scheme, fields = ?redacted, "verifying webhook signature");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn verify_hmac_sha256_accepts_matching_signature() {
+        let secret = SecretString::new("app_secret".to_owned().into());
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"app_secret").unwrap();
+        mac.update(b"payload-bytes");
+        let signature = hex_encode(&mac.finalize().into_bytes());
+
+        assert!(verify_hmac_sha256(b"payload-bytes", &signature, &secret).is_ok());
+    }
+
+    #[test]
+    fn verify_hmac_sha256_rejects_tampered_payload() {
+        let secret = SecretString::new("app_secret".to_owned().into());
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"app_secret").unwrap();
+        mac.update(b"payload-bytes");
+        let signature = hex_encode(&mac.finalize().into_bytes());
+
+        assert!(verify_hmac_sha256(b"tampered-bytes", &signature, &secret).is_err());
+    }
+
+    #[test]
+    fn verify_hmac_sha256_rejects_non_hex_signature() {
+        let secret = SecretString::new("app_secret".to_owned().into());
+        assert!(verify_hmac_sha256(b"payload-bytes", "not-hex!!", &secret).is_err());
+    }
+
+    #[test]
+    fn verify_sorted_field_hmac_sha256_sorts_fields_by_key_and_signs_them() {
+        let secret = SecretString::new("store_passwd".to_owned().into());
+        let mut fields = HashMap::new();
+        fields.insert("status".to_owned(), "VALID".to_owned());
+        fields.insert("tran_id".to_owned(), "TXN123".to_owned());
+        fields.insert("verify_key".to_owned(), "status,tran_id".to_owned());
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"store_passwd").unwrap();
+        mac.update(b"status=VALID&tran_id=TXN123");
+        let signature = hex_encode(&mac.finalize().into_bytes());
+        fields.insert("verify_sign".to_owned(), signature);
+
+        assert!(
+            verify_sorted_field_hmac_sha256(&fields, "verify_key", "verify_sign", &secret).is_ok()
+        );
+    }
+
+    #[test]
+    fn verify_sorted_field_hmac_sha256_rejects_tampered_field() {
+        let secret = SecretString::new("store_passwd".to_owned().into());
+        let mut fields = HashMap::new();
+        fields.insert("status".to_owned(), "VALID".to_owned());
+        fields.insert("tran_id".to_owned(), "TXN123".to_owned());
+        fields.insert("verify_key".to_owned(), "status,tran_id".to_owned());
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"store_passwd").unwrap();
+        mac.update(b"status=VALID&tran_id=TXN123");
+        let signature = hex_encode(&mac.finalize().into_bytes());
+        fields.insert("verify_sign".to_owned(), signature);
+        fields.insert("tran_id".to_owned(), "TAMPERED".to_owned());
+
+        assert!(
+            verify_sorted_field_hmac_sha256(&fields, "verify_key", "verify_sign", &secret).is_err()
+        );
+    }
+
+    fn sslcommerz_style_fields(secret: &str) -> HashMap<String, String> {
+        let mut fields = HashMap::new();
+        fields.insert("status".to_owned(), "VALID".to_owned());
+        fields.insert("tran_id".to_owned(), "TXN123".to_owned());
+        fields.insert("verify_key".to_owned(), "status,tran_id".to_owned());
+
+        let mut pairs = vec![
+            "status=VALID".to_owned(),
+            "tran_id=TXN123".to_owned(),
+            format!("store_passwd={:x}", md5::compute(secret)),
+        ];
+        pairs.sort_unstable();
+        let signature = format!("{:x}", md5::compute(pairs.join("&")));
+        fields.insert("verify_sign".to_owned(), signature);
+        fields
+    }
+
+    #[test]
+    fn verify_sorted_field_md5_accepts_sslcommerz_style_signature() {
+        let secret = SecretString::new("store_passwd".to_owned().into());
+        let fields = sslcommerz_style_fields("store_passwd");
+
+        assert!(verify_sorted_field_md5(&fields, "verify_key", "verify_sign", &secret).is_ok());
+    }
+
+    #[test]
+    fn verify_sorted_field_md5_accepts_uppercase_hex_signature() {
+        let secret = SecretString::new("store_passwd".to_owned().into());
+        let mut fields = sslcommerz_style_fields("store_passwd");
+        let uppercased = fields["verify_sign"].to_ascii_uppercase();
+        fields.insert("verify_sign".to_owned(), uppercased);
+
+        assert!(verify_sorted_field_md5(&fields, "verify_key", "verify_sign", &secret).is_ok());
+    }
+
+    #[test]
+    fn verify_sorted_field_md5_rejects_tampered_field() {
+        let secret = SecretString::new("store_passwd".to_owned().into());
+        let mut fields = sslcommerz_style_fields("store_passwd");
+        fields.insert("tran_id".to_owned(), "TAMPERED".to_owned());
+
+        assert!(verify_sorted_field_md5(&fields, "verify_key", "verify_sign", &secret).is_err());
+    }
+}
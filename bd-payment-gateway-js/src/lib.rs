@@ -386,6 +386,7 @@ pub fn create_aamarpay_client(config_json: String) -> napi::Result<AamarpayClien
         signature_key: SecretString::new(cfg.signature_key.into()),
         environment: parse_environment(cfg.environment)?,
         http_settings: parse_http_settings(cfg.http_settings)?,
+        refund_endpoint: None,
     };
 
     let inner = bd_payment_gateway_aamarpay::AamarpayClient::new(config).map_err(to_napi_error)?;
@@ -1,12 +1,14 @@
 use std::str::FromStr;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bd_payment_gateway_core::{
     BdPaymentError, Currency, Environment, HttpClient, HttpSettings, InitiatePaymentResponse,
-    PaymentProvider, PaymentStatus, RefundResponse, RefundStatus, Result, VerifyPaymentResponse,
+    LineItem, PaymentProvider, PaymentStatus, RefundResponse, RefundStatus, Result,
+    VerifyPaymentResponse, WebhookPayload, WebhookVerifier, constant_time_eq,
     http::add_default_headers,
 };
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use rust_decimal::Decimal;
 use secrecy::{ExposeSecret, SecretString};
@@ -18,6 +20,13 @@ use url::Url;
 const SANDBOX_BASE: &str = "https://api-sandbox.portwallet.com";
 const PRODUCTION_BASE: &str = "https://api.portwallet.com";
 
+/// Default tolerance for [`PortwalletClient`]'s webhook timestamp check: an `x-app-timestamp`
+/// more than this far from "now" (in either direction) is rejected as stale/replayed, matching
+/// the request's default ±5 minute window.
+const DEFAULT_WEBHOOK_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%d%H%M%S";
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub app_key: String,
@@ -51,6 +60,7 @@ pub struct PortwalletClient {
     config: Config,
     http: HttpClient,
     base_url: Url,
+    webhook_tolerance: Duration,
 }
 
 impl PortwalletClient {
@@ -62,9 +72,17 @@ impl PortwalletClient {
             config,
             http,
             base_url,
+            webhook_tolerance: DEFAULT_WEBHOOK_TOLERANCE,
         })
     }
 
+    /// Overrides the replay-tolerance window [`WebhookVerifier::verify`] allows between an
+    /// `x-app-timestamp` and "now", in place of the [`DEFAULT_WEBHOOK_TOLERANCE`] (±5 minutes).
+    pub fn with_webhook_tolerance(mut self, tolerance: Duration) -> Self {
+        self.webhook_tolerance = tolerance;
+        self
+    }
+
     fn auth_headers(
         &self,
         correlation_id: Option<&str>,
@@ -72,7 +90,7 @@ impl PortwalletClient {
     ) -> Result<HeaderMap> {
         let timestamp = ts_override
             .map(ToOwned::to_owned)
-            .unwrap_or_else(|| Utc::now().format("%Y%m%d%H%M%S").to_string());
+            .unwrap_or_else(|| Utc::now().format(TIMESTAMP_FORMAT).to_string());
         let signature = generate_signature(self.config.app_secret.expose_secret(), &timestamp);
 
         let mut headers = HeaderMap::new();
@@ -119,6 +137,92 @@ pub struct CustomerInfo {
     pub country: Option<String>,
 }
 
+impl CustomerInfo {
+    pub fn builder() -> CustomerInfoBuilder {
+        CustomerInfoBuilder::default()
+    }
+}
+
+/// Fluent builder for [`CustomerInfo`]. `build()` requires `name`, `email`, and `phone` (the
+/// fields PortWallet rejects an invoice create without); the remaining fields stay optional.
+#[derive(Debug, Clone, Default)]
+pub struct CustomerInfoBuilder {
+    name: Option<String>,
+    email: Option<String>,
+    phone: Option<String>,
+    address: Option<String>,
+    city: Option<String>,
+    zip_code: Option<String>,
+    country: Option<String>,
+}
+
+impl CustomerInfoBuilder {
+    pub fn name(mut self, v: impl Into<String>) -> Self {
+        self.name = Some(v.into());
+        self
+    }
+
+    pub fn email(mut self, v: impl Into<String>) -> Self {
+        self.email = Some(v.into());
+        self
+    }
+
+    pub fn phone(mut self, v: impl Into<String>) -> Self {
+        self.phone = Some(v.into());
+        self
+    }
+
+    pub fn address(mut self, v: impl Into<String>) -> Self {
+        self.address = Some(v.into());
+        self
+    }
+
+    pub fn city(mut self, v: impl Into<String>) -> Self {
+        self.city = Some(v.into());
+        self
+    }
+
+    pub fn zip_code(mut self, v: impl Into<String>) -> Self {
+        self.zip_code = Some(v.into());
+        self
+    }
+
+    pub fn country(mut self, v: impl Into<String>) -> Self {
+        self.country = Some(v.into());
+        self
+    }
+
+    fn require(field: Option<String>, message: &str, hint: &str) -> Result<String> {
+        field
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| BdPaymentError::validation(message.to_owned(), hint.to_owned()))
+    }
+
+    pub fn build(self) -> Result<CustomerInfo> {
+        Ok(CustomerInfo {
+            name: Self::require(
+                self.name,
+                "name is required to build a PortWallet CustomerInfo.",
+                "Call .name(..) before .build().",
+            )?,
+            email: Self::require(
+                self.email,
+                "email is required to build a PortWallet CustomerInfo.",
+                "Call .email(..) before .build().",
+            )?,
+            phone: Self::require(
+                self.phone,
+                "phone is required to build a PortWallet CustomerInfo.",
+                "Call .phone(..) before .build().",
+            )?,
+            address: self.address,
+            city: self.city,
+            zip_code: self.zip_code,
+            country: self.country,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InitiatePaymentRequest {
     pub order: String,
@@ -128,6 +232,10 @@ pub struct InitiatePaymentRequest {
     pub ipn_url: Url,
     pub reference: Option<String>,
     pub customer: CustomerInfo,
+    /// Optional per-item breakdown of `amount`. When non-empty, [`Self::validate`] requires
+    /// `sum(item.unit_price.amount * item.quantity)` to equal `amount` exactly.
+    #[serde(default)]
+    pub items: Vec<LineItem>,
     pub correlation_id: Option<String>,
 }
 
@@ -139,20 +247,154 @@ impl InitiatePaymentRequest {
                 "Use your unique order/invoice identifier.",
             ));
         }
-        if Decimal::from_str(&self.amount).is_err() {
-            return Err(BdPaymentError::validation(
+        let amount = Decimal::from_str(&self.amount).map_err(|_| {
+            BdPaymentError::validation(
                 "amount must be a numeric decimal string for PortWallet.",
                 "Use values like '100.00'.",
-            ));
-        }
+            )
+        })?;
         if self.customer.name.trim().is_empty() || self.customer.phone.trim().is_empty() {
             return Err(BdPaymentError::validation(
                 "customer.name and customer.phone are required for PortWallet.",
                 "Provide customer identity fields as documented by PortWallet.",
             ));
         }
+        if !self.items.is_empty() {
+            let items_total: Decimal = self
+                .items
+                .iter()
+                .map(|item| item.unit_price.amount * Decimal::from(item.quantity))
+                .sum();
+            if items_total != amount {
+                return Err(BdPaymentError::validation(
+                    format!(
+                        "items total {items_total} does not reconcile with declared amount {amount}."
+                    ),
+                    "Ensure sum(item.unit_price.amount * item.quantity) equals amount exactly.",
+                ));
+            }
+        }
         Ok(())
     }
+
+    pub fn builder() -> InitiatePaymentRequestBuilder {
+        InitiatePaymentRequestBuilder::default()
+    }
+}
+
+/// Fluent builder for [`InitiatePaymentRequest`]. `build()` runs the same validation as
+/// [`InitiatePaymentRequest::validate`], so a successfully built request is always send-ready.
+#[derive(Debug, Clone, Default)]
+pub struct InitiatePaymentRequestBuilder {
+    order: Option<String>,
+    amount: Option<String>,
+    currency: Option<String>,
+    redirect_url: Option<Url>,
+    ipn_url: Option<Url>,
+    reference: Option<String>,
+    customer: Option<CustomerInfo>,
+    items: Vec<LineItem>,
+    correlation_id: Option<String>,
+}
+
+impl InitiatePaymentRequestBuilder {
+    pub fn order(mut self, v: impl Into<String>) -> Self {
+        self.order = Some(v.into());
+        self
+    }
+
+    pub fn amount(mut self, v: impl Into<String>) -> Self {
+        self.amount = Some(v.into());
+        self
+    }
+
+    pub fn currency(mut self, v: impl Into<String>) -> Self {
+        self.currency = Some(v.into());
+        self
+    }
+
+    pub fn redirect_url(mut self, v: Url) -> Self {
+        self.redirect_url = Some(v);
+        self
+    }
+
+    pub fn ipn_url(mut self, v: Url) -> Self {
+        self.ipn_url = Some(v);
+        self
+    }
+
+    pub fn reference(mut self, v: impl Into<String>) -> Self {
+        self.reference = Some(v.into());
+        self
+    }
+
+    pub fn customer(mut self, v: CustomerInfo) -> Self {
+        self.customer = Some(v);
+        self
+    }
+
+    /// Appends one line item; call repeatedly to build up the full breakdown.
+    pub fn item(mut self, v: LineItem) -> Self {
+        self.items.push(v);
+        self
+    }
+
+    pub fn items(mut self, v: Vec<LineItem>) -> Self {
+        self.items = v;
+        self
+    }
+
+    pub fn correlation_id(mut self, v: impl Into<String>) -> Self {
+        self.correlation_id = Some(v.into());
+        self
+    }
+
+    pub fn build(self) -> Result<InitiatePaymentRequest> {
+        let req = InitiatePaymentRequest {
+            order: self.order.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "order is required to build a PortWallet InitiatePaymentRequest.",
+                    "Call .order(..) before .build().",
+                )
+            })?,
+            amount: self.amount.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "amount is required to build a PortWallet InitiatePaymentRequest.",
+                    "Call .amount(..) before .build().",
+                )
+            })?,
+            currency: self.currency.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "currency is required to build a PortWallet InitiatePaymentRequest.",
+                    "Call .currency(..) before .build().",
+                )
+            })?,
+            redirect_url: self.redirect_url.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "redirect_url is required to build a PortWallet InitiatePaymentRequest.",
+                    "Call .redirect_url(..) before .build().",
+                )
+            })?,
+            ipn_url: self.ipn_url.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "ipn_url is required to build a PortWallet InitiatePaymentRequest.",
+                    "Call .ipn_url(..) before .build().",
+                )
+            })?,
+            reference: self.reference,
+            customer: self.customer.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "customer is required to build a PortWallet InitiatePaymentRequest.",
+                    "Call .customer(..) before .build().",
+                )
+            })?,
+            items: self.items,
+            correlation_id: self.correlation_id,
+        };
+
+        req.validate()?;
+        Ok(req)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -179,6 +421,28 @@ struct InvoiceCreateRequest<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     reference: Option<&'a str>,
     customer: &'a CustomerInfo,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    items: Vec<InvoiceLineItem<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct InvoiceLineItem<'a> {
+    name: &'a str,
+    quantity: u32,
+    unit_price: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sku: Option<&'a str>,
+}
+
+impl<'a> From<&'a LineItem> for InvoiceLineItem<'a> {
+    fn from(item: &'a LineItem) -> Self {
+        Self {
+            name: &item.name,
+            quantity: item.quantity,
+            unit_price: item.unit_price.amount.to_string(),
+            sku: item.sku.as_deref(),
+        }
+    }
 }
 
 #[async_trait]
@@ -186,6 +450,9 @@ impl PaymentProvider for PortwalletClient {
     type InitiateRequest = InitiatePaymentRequest;
     type VerifyRequest = VerifyPaymentRequest;
     type RefundRequest = RefundRequest;
+    type AuthorizeRequest = ();
+    type CaptureRequest = ();
+    type VoidRequest = ();
 
     async fn initiate_payment(
         &self,
@@ -209,6 +476,7 @@ impl PaymentProvider for PortwalletClient {
             ipn_url: req.ipn_url.as_str(),
             reference: req.reference.as_deref(),
             customer: &req.customer,
+            items: req.items.iter().map(InvoiceLineItem::from).collect(),
         };
 
         let raw: Value = self.http.post_json(&url, headers, &body).await?;
@@ -254,6 +522,7 @@ impl PaymentProvider for PortwalletClient {
             provider_reference,
             raw,
             request_id: req.correlation_id.clone(),
+            network_transaction_id: None,
         })
     }
 
@@ -297,8 +566,11 @@ impl PaymentProvider for PortwalletClient {
             .and_then(Value::as_str)
             .map(parse_currency);
 
+        let status = map_payment_status(&raw);
+        let failure_reason = failure_reason_for(&status, &raw);
+
         Ok(VerifyPaymentResponse {
-            status: map_payment_status(&raw),
+            status,
             provider_reference: req.invoice_id.clone(),
             amount,
             currency: currency.clone(),
@@ -307,6 +579,8 @@ impl PaymentProvider for PortwalletClient {
                 .map(|(amount, currency)| bd_payment_gateway_core::Money { amount, currency }),
             raw,
             request_id: req.correlation_id.clone(),
+            failure_reason,
+            network_transaction_id: None,
         })
     }
 
@@ -379,12 +653,77 @@ fn generate_signature(secret: &str, timestamp: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+impl WebhookVerifier for PortwalletClient {
+    /// Authenticates a PortWallet IPN POST by recomputing `sha256(app_secret || timestamp)` from
+    /// its `x-app-signature`/`x-app-timestamp` headers, the same scheme [`Self::auth_headers`]
+    /// uses to sign outbound requests, and comparing it to the header in constant time. Also
+    /// rejects a timestamp more than `webhook_tolerance` away from "now" to block replay of an
+    /// old, genuinely-signed callback.
+    fn verify(&self, raw_body: &[u8], headers: &HeaderMap) -> Result<WebhookPayload> {
+        let timestamp = header_str(headers, "x-app-timestamp")?;
+        let signature = header_str(headers, "x-app-signature")?;
+
+        let parsed_timestamp =
+            NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).map_err(|e| {
+                BdPaymentError::validation(
+                    format!("Invalid PortWallet webhook timestamp: {e}"),
+                    "Expected x-app-timestamp in YYYYMMDDHHMMSS UTC format.",
+                )
+            })?;
+        let age = Utc::now().naive_utc() - parsed_timestamp;
+        if age.abs() > chrono::Duration::from_std(self.webhook_tolerance).unwrap_or_default() {
+            return Err(BdPaymentError::validation(
+                "PortWallet webhook timestamp is outside the allowed tolerance window.",
+                "Reject this callback; it may be a replay of an old notification.",
+            ));
+        }
+
+        let expected = generate_signature(self.config.app_secret.expose_secret(), timestamp);
+        if !constant_time_eq(expected.as_bytes(), signature.as_bytes()) {
+            return Err(BdPaymentError::validation(
+                "PortWallet webhook signature does not match the configured app_secret.",
+                "Reject this callback; it did not originate from PortWallet.",
+            ));
+        }
+
+        let payload: Value = serde_json::from_slice(raw_body).map_err(|e| {
+            BdPaymentError::validation(
+                format!("PortWallet webhook body is not valid JSON: {e}"),
+                "Ensure the IPN POST body is forwarded unmodified.",
+            )
+        })?;
+
+        Ok(WebhookPayload {
+            provider: "portwallet".to_owned(),
+            payload,
+        })
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str> {
+    headers
+        .get(name)
+        .ok_or_else(|| {
+            BdPaymentError::validation(
+                format!("PortWallet webhook is missing the {name} header."),
+                "Forward the IPN request headers unmodified to the verifier.",
+            )
+        })?
+        .to_str()
+        .map_err(|e| {
+            BdPaymentError::validation(
+                format!("PortWallet webhook {name} header is not valid ASCII: {e}"),
+                "Forward the IPN request headers unmodified to the verifier.",
+            )
+        })
+}
+
 fn parse_currency(raw: &str) -> Currency {
     match raw.to_ascii_uppercase().as_str() {
         "BDT" => Currency::Bdt,
         "USD" => Currency::Usd,
         "EUR" => Currency::Eur,
-        other => Currency::Other(other.to_owned()),
+        other => Currency::other(other.to_owned()),
     }
 }
 
@@ -409,6 +748,18 @@ fn map_payment_status(raw: &Value) -> PaymentStatus {
     }
 }
 
+/// Extracts a decline reason out of the same `/data/...` shaped body [`map_payment_status`] read
+/// its status from, for the subset of calls where that status turned out to be `Failed`.
+fn failure_reason_for(
+    status: &PaymentStatus,
+    raw: &Value,
+) -> Option<bd_payment_gateway_core::FailureReason> {
+    matches!(status, PaymentStatus::Failed).then(|| {
+        let body = raw.pointer("/data").unwrap_or(raw);
+        bd_payment_gateway_core::extract_failure_reason(body, "PortWallet reported a failed payment.")
+    })
+}
+
 fn map_refund_status(raw: &Value) -> RefundStatus {
     let status = raw
         .pointer("/data/status")
@@ -453,6 +804,63 @@ fn provider_code(raw: &Value) -> Option<String> {
         })
 }
 
+/// Minimal JSON shape accepted by this provider's [`bd_payment_gateway_core::registry`]
+/// registration. Deliberately narrower than [`Config`]: it covers the fields every merchant must
+/// supply and leaves `http_settings` at [`HttpSettings::default`], since the registry's `build`
+/// hook has no channel for per-caller tuning knobs.
+#[derive(Deserialize)]
+struct RegistryConfig {
+    app_key: String,
+    app_secret: String,
+    environment: Environment,
+}
+
+fn registry_build(
+    config: bd_payment_gateway_core::registry::ProviderConfigJson,
+) -> Result<Box<dyn bd_payment_gateway_core::DynPaymentProvider>> {
+    let cfg: RegistryConfig = serde_json::from_value(config).map_err(|e| {
+        BdPaymentError::config(
+            format!("Invalid PortWallet registry config: {e}"),
+            "Provide app_key, app_secret, and environment.",
+        )
+    })?;
+    let client = PortwalletClient::new(Config {
+        app_key: cfg.app_key,
+        app_secret: SecretString::new(cfg.app_secret.into()),
+        environment: cfg.environment,
+        http_settings: HttpSettings::default(),
+    })?;
+    Ok(Box::new(client))
+}
+
+/// The registry's `parse_webhook` hook only normalizes the posted status/reference fields; it has
+/// no access to the raw headers a signature check needs, so it does not authenticate the
+/// callback. Callers that receive the raw IPN request should authenticate it first with
+/// [`PortwalletClient`]'s [`WebhookVerifier`] impl (or call `verify_payment` to confirm settlement
+/// independently) before trusting this normalized event.
+fn registry_parse_webhook(
+    payload: &Value,
+) -> Result<bd_payment_gateway_core::registry::NormalizedEvent> {
+    let fields = bd_payment_gateway_core::registry::flatten_object(payload);
+    let status_raw = fields.get("status").cloned().unwrap_or_default();
+    let provider_reference = fields.get("invoice_id").cloned().unwrap_or_default();
+
+    Ok(bd_payment_gateway_core::registry::NormalizedEvent {
+        provider: "portwallet",
+        status: PaymentStatus::from_keyword(&status_raw),
+        provider_reference,
+        raw: payload.clone(),
+    })
+}
+
+inventory::submit! {
+    bd_payment_gateway_core::registry::ProviderRegistration {
+        name: "portwallet",
+        build: registry_build,
+        parse_webhook: registry_parse_webhook,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::generate_signature;
@@ -479,6 +887,102 @@ mod tests {
         assert_ne!(s1, s2);
     }
 
+    fn sample_request(amount: &str, items: Vec<LineItem>) -> InitiatePaymentRequest {
+        InitiatePaymentRequest {
+            order: "order-1".to_owned(),
+            amount: amount.to_owned(),
+            currency: "BDT".to_owned(),
+            redirect_url: Url::parse("https://merchant.test/success").expect("url"),
+            ipn_url: Url::parse("https://merchant.test/ipn").expect("url"),
+            reference: None,
+            customer: CustomerInfo {
+                name: "Demo User".to_owned(),
+                email: "demo@example.com".to_owned(),
+                phone: "01700000000".to_owned(),
+                address: None,
+                city: None,
+                zip_code: None,
+                country: None,
+            },
+            items,
+            correlation_id: None,
+        }
+    }
+
+    fn line_item(name: &str, quantity: u32, unit_price: &str) -> LineItem {
+        LineItem {
+            name: name.to_owned(),
+            quantity,
+            unit_price: bd_payment_gateway_core::Money::new(
+                Decimal::from_str(unit_price).expect("decimal"),
+                Currency::Bdt,
+            ),
+            sku: None,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_items_that_reconcile_with_amount() {
+        let req = sample_request(
+            "250.00",
+            vec![line_item("Widget", 2, "100.00"), line_item("Gadget", 1, "50.00")],
+        );
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_items_that_do_not_reconcile_with_amount() {
+        let req = sample_request("100.00", vec![line_item("Widget", 2, "100.00")]);
+        let err = req.validate().expect_err("mismatched total should be rejected");
+        assert!(err.to_string().contains("does not reconcile"));
+    }
+
+    #[test]
+    fn builder_builds_valid_request_with_defaulted_optionals() {
+        let req = InitiatePaymentRequest::builder()
+            .order("order-1")
+            .amount("100.00")
+            .currency("BDT")
+            .redirect_url(Url::parse("https://merchant.test/success").expect("url"))
+            .ipn_url(Url::parse("https://merchant.test/ipn").expect("url"))
+            .customer(
+                CustomerInfo::builder()
+                    .name("Demo User")
+                    .email("demo@example.com")
+                    .phone("01700000000")
+                    .build()
+                    .expect("customer should build"),
+            )
+            .build()
+            .expect("builder should produce a valid request");
+
+        assert_eq!(req.order, "order-1");
+        assert!(req.reference.is_none());
+        assert!(req.items.is_empty());
+    }
+
+    #[test]
+    fn builder_rejects_missing_required_field() {
+        let err = InitiatePaymentRequest::builder()
+            .amount("100.00")
+            .currency("BDT")
+            .build()
+            .expect_err("order is required");
+
+        assert!(matches!(err, BdPaymentError::ValidationError { .. }));
+    }
+
+    #[test]
+    fn customer_info_builder_rejects_missing_phone() {
+        let err = CustomerInfo::builder()
+            .name("Demo User")
+            .email("demo@example.com")
+            .build()
+            .expect_err("phone is required");
+
+        assert!(matches!(err, BdPaymentError::ValidationError { .. }));
+    }
+
     #[tokio::test]
     async fn verify_payment_maps_paid_status() {
         let server = MockServer::start();
@@ -518,4 +1022,70 @@ mod tests {
             Some("100.00")
         );
     }
+
+    fn test_client() -> PortwalletClient {
+        PortwalletClient::new(Config {
+            app_key: "k".to_owned(),
+            app_secret: SecretString::new("s".to_owned().into()),
+            environment: Environment::Sandbox,
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client")
+    }
+
+    fn webhook_headers(timestamp: &str, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-app-timestamp"),
+            HeaderValue::from_str(timestamp).expect("timestamp header"),
+        );
+        headers.insert(
+            HeaderName::from_static("x-app-signature"),
+            HeaderValue::from_str(signature).expect("signature header"),
+        );
+        headers
+    }
+
+    #[test]
+    fn webhook_verify_accepts_a_freshly_signed_payload() {
+        let client = test_client();
+        let timestamp = Utc::now().format(TIMESTAMP_FORMAT).to_string();
+        let signature = generate_signature("s", &timestamp);
+        let headers = webhook_headers(&timestamp, &signature);
+        let body = br#"{"invoice_id":"INV-1","status":"PAID"}"#;
+
+        let parsed = client.verify(body, &headers).expect("verifies");
+
+        assert_eq!(parsed.provider, "portwallet");
+        assert_eq!(parsed.payload["invoice_id"], "INV-1");
+    }
+
+    #[test]
+    fn webhook_verify_rejects_a_tampered_signature() {
+        let client = test_client();
+        let timestamp = Utc::now().format(TIMESTAMP_FORMAT).to_string();
+        let headers = webhook_headers(&timestamp, "0000000000000000000000000000000000000000000000000000000000000000");
+
+        let err = client
+            .verify(br#"{"invoice_id":"INV-1"}"#, &headers)
+            .expect_err("mismatched signature should be rejected");
+
+        assert!(err.to_string().contains("signature"));
+    }
+
+    #[test]
+    fn webhook_verify_rejects_a_stale_timestamp() {
+        let client = test_client();
+        let timestamp = (Utc::now() - chrono::Duration::minutes(10))
+            .format(TIMESTAMP_FORMAT)
+            .to_string();
+        let signature = generate_signature("s", &timestamp);
+        let headers = webhook_headers(&timestamp, &signature);
+
+        let err = client
+            .verify(br#"{"invoice_id":"INV-1"}"#, &headers)
+            .expect_err("stale timestamp should be rejected");
+
+        assert!(err.to_string().contains("tolerance"));
+    }
 }
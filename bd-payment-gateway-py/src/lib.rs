@@ -16,13 +16,87 @@ use pyo3::types::{PyAny, PyModule};
 use secrecy::SecretString;
 use serde::{Deserialize, de::DeserializeOwned};
 use serde_json::{Value, json};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 use url::Url;
 
 static RUNTIME: Lazy<Runtime> =
     Lazy::new(|| Runtime::new().expect("tokio runtime should initialize for Python binding"));
 
+const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Terminal state of an in-progress or completed `initiate_payment` call, keyed by
+/// `(provider, idempotency_key)`. Mirrors the Rust-side `IdempotencyStore`'s `New`/`InFlight`/
+/// `Completed` tri-state: a plain check-then-act cache would let two concurrent Python calls with
+/// the same key both miss and both reach the live provider, double-charging the customer.
+enum IdempotentEntry {
+    InFlight,
+    Completed(Instant, InitiatePaymentResponse),
+}
+
+/// Process-wide `initiate_payment` replay cache keyed by `(provider, idempotency_key)`, so a
+/// retried Python request returns the original response instead of double-charging the customer.
+static IDEMPOTENCY_CACHE: Lazy<Mutex<HashMap<(String, String), IdempotentEntry>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Atomically claims `idempotency_key` for `provider`: a never-seen or TTL-expired key is marked
+/// `InFlight` and this returns `Ok(None)` so the caller should proceed to call the provider; a key
+/// with a result already recorded within `ttl` returns it directly; a key another concurrent call
+/// is still processing returns `Err` instead of letting this call also reach the live provider.
+/// A claimed key must eventually reach [`store_idempotent_response`] or [`release_idempotent_claim`],
+/// or it stays `InFlight` forever.
+fn claim_idempotent_request(
+    provider: &str,
+    idempotency_key: &str,
+    ttl: Duration,
+) -> PyResult<Option<InitiatePaymentResponse>> {
+    let mut cache = IDEMPOTENCY_CACHE
+        .lock()
+        .expect("idempotency cache mutex poisoned");
+    cache.retain(|_, entry| match entry {
+        IdempotentEntry::Completed(stored_at, _) => stored_at.elapsed() < ttl,
+        IdempotentEntry::InFlight => true,
+    });
+
+    let cache_key = (provider.to_owned(), idempotency_key.to_owned());
+    match cache.get(&cache_key) {
+        Some(IdempotentEntry::Completed(_, response)) => Ok(Some(response.clone())),
+        Some(IdempotentEntry::InFlight) => Err(PaymentGatewayError::new_err(format!(
+            "Another {provider} initiate_payment call with idempotency key {idempotency_key:?} is already in flight."
+        ))),
+        None => {
+            cache.insert(cache_key, IdempotentEntry::InFlight);
+            Ok(None)
+        }
+    }
+}
+
+fn store_idempotent_response(
+    provider: &str,
+    idempotency_key: &str,
+    response: &InitiatePaymentResponse,
+) {
+    let mut cache = IDEMPOTENCY_CACHE
+        .lock()
+        .expect("idempotency cache mutex poisoned");
+    cache.insert(
+        (provider.to_owned(), idempotency_key.to_owned()),
+        IdempotentEntry::Completed(Instant::now(), response.clone()),
+    );
+}
+
+/// Releases a claim made by [`claim_idempotent_request`] without recording a result, so a failed
+/// provider call (network error, validation error, etc.) doesn't wedge the key `InFlight` forever
+/// and block every future retry.
+fn release_idempotent_claim(provider: &str, idempotency_key: &str) {
+    let mut cache = IDEMPOTENCY_CACHE
+        .lock()
+        .expect("idempotency cache mutex poisoned");
+    cache.remove(&(provider.to_owned(), idempotency_key.to_owned()));
+}
+
 pyo3::create_exception!(
     _bd_payment_gateway_py,
     PaymentGatewayError,
@@ -35,6 +109,64 @@ struct EnvInput {
     custom_base_url: Option<String>,
 }
 
+/// Locale for translating the `PaymentGatewayError` `message`/`hint` surfaced to Python.
+/// Mirrors craftgate's `WithLocalization("en"/"tr")` option, but scoped to "en"/"bn" since this
+/// gateway only targets Bangladeshi merchants today.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Locale {
+    En,
+    Bn,
+}
+
+fn parse_locale(raw: Option<String>, what: &str) -> PyResult<Locale> {
+    parse_locale_raw(raw, what).map_err(PyValueError::new_err)
+}
+
+fn parse_locale_raw(raw: Option<String>, what: &str) -> std::result::Result<Locale, String> {
+    match raw.as_deref() {
+        None | Some("en") => Ok(Locale::En),
+        Some("bn") => Ok(Locale::Bn),
+        Some(other) => Err(format!("{what}.locale must be \"en\" or \"bn\", got {other:?}")),
+    }
+}
+
+/// Generic, code-keyed hint translations for the common `ConfigError`/`ValidationError`/
+/// `Unsupported`/`ParseError` codes. These hints are short repo-authored guidance sentences
+/// rather than provider text, so a static per-code table covers them. `HttpError`/`ProviderError`
+/// codes fall through unchanged, since their hints can reference live provider details.
+fn localized_hint(code: &str, hint: &str, locale: Locale) -> String {
+    if locale == Locale::En {
+        return hint.to_owned();
+    }
+    match code {
+        "CONFIG_INVALID" => {
+            "মার্চেন্ট কনফিগারেশন পরীক্ষা করে প্রয়োজনীয় ক্ষেত্রগুলো পূরণ করুন।".to_owned()
+        }
+        "VALIDATION_FAILED" => "অনুরোধের ক্ষেত্রগুলো যাচাই করে আবার চেষ্টা করুন।".to_owned(),
+        "UNSUPPORTED_OPERATION" => {
+            "এই গেটওয়ের জন্য এই অপারেশনটি বর্তমানে সমর্থিত নয়।".to_owned()
+        }
+        "PARSE_FAILED" => {
+            "প্রোভাইডারের রেসপন্স পার্স করা যায়নি; সাপোর্ট টিমের সাথে যোগাযোগ করুন।".to_owned()
+        }
+        _ => hint.to_owned(),
+    }
+}
+
+/// Translates the small set of fixed, repo-authored canned messages this crate emits, leaving
+/// any other message -- dynamically composed or provider-supplied -- untouched.
+fn localized_message(message: &str, locale: Locale) -> String {
+    if locale == Locale::En {
+        return message.to_owned();
+    }
+    match message {
+        "SSLCOMMERZ webhook verify_sign did not match the recomputed signature." => {
+            "SSLCOMMERZ ওয়েবহুকের verify_sign পুনঃগণনাকৃত স্বাক্ষরের সাথে মেলেনি।".to_owned()
+        }
+        other => other.to_owned(),
+    }
+}
+
 #[derive(Deserialize)]
 struct HttpSettingsInput {
     timeout_ms: Option<u64>,
@@ -42,8 +174,12 @@ struct HttpSettingsInput {
     initial_backoff_ms: Option<u64>,
     max_backoff_ms: Option<u64>,
     user_agent: Option<String>,
+    /// How long a cached `initiate_payment` response stays eligible for idempotency-key replay.
+    /// Defaults to [`DEFAULT_IDEMPOTENCY_TTL`] (24h) when omitted.
+    idempotency_ttl_ms: Option<u64>,
 }
 
+#[derive(Clone)]
 #[pyclass]
 struct InitiatePaymentResponse {
     #[pyo3(get)]
@@ -84,6 +220,22 @@ struct RefundResponse {
     request_id: Option<String>,
 }
 
+#[pyclass]
+struct WebhookEvent {
+    #[pyo3(get)]
+    status: String,
+    #[pyo3(get)]
+    provider_reference: String,
+    #[pyo3(get)]
+    amount: Option<String>,
+    #[pyo3(get)]
+    currency: Option<String>,
+    #[pyo3(get)]
+    verified: bool,
+    #[pyo3(get)]
+    raw: String,
+}
+
 fn py_input_to_json(input: &Bound<'_, PyAny>, what: &str) -> PyResult<String> {
     if let Ok(raw) = input.extract::<String>() {
         return Ok(raw);
@@ -108,6 +260,80 @@ fn parse_json_input<T: DeserializeOwned>(input: &Bound<'_, PyAny>, what: &str) -
     serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(format!("Invalid {what}: {e}")))
 }
 
+/// Like [`parse_json_input`], but also lifts out the optional `idempotency_key` field so
+/// `initiate_payment` can check the replay cache before hitting the provider. The provider's own
+/// request type doesn't declare `idempotency_key`, but it's a harmless extra field to it since
+/// none of these structs use `deny_unknown_fields`.
+fn parse_initiate_request<T: DeserializeOwned>(
+    input: &Bound<'_, PyAny>,
+    what: &str,
+) -> PyResult<(T, Option<String>)> {
+    let raw = py_input_to_json(input, what)?;
+    let value: Value = serde_json::from_str(&raw)
+        .map_err(|e| PyValueError::new_err(format!("Invalid {what}: {e}")))?;
+    let idempotency_key = value
+        .get("idempotency_key")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
+    let request: T = serde_json::from_value(value)
+        .map_err(|e| PyValueError::new_err(format!("Invalid {what}: {e}")))?;
+    Ok((request, idempotency_key))
+}
+
+/// Decodes a webhook/IPN body into a flat field map, the way `eopayment` always decodes the
+/// callback as a query string regardless of whether the gateway posted form-encoded or JSON data.
+/// Accepts a Python `str`/`bytes` payload as-is, or dumps a mapping/object to JSON first.
+fn decode_webhook_body(body: &Bound<'_, PyAny>) -> PyResult<(HashMap<String, String>, Value)> {
+    let raw = if let Ok(bytes) = body.extract::<Vec<u8>>() {
+        String::from_utf8(bytes)
+            .map_err(|e| PyValueError::new_err(format!("webhook body is not valid UTF-8: {e}")))?
+    } else {
+        py_input_to_json(body, "webhook body")?
+    };
+
+    let trimmed = raw.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        let value: Value = serde_json::from_str(&raw)
+            .map_err(|e| PyValueError::new_err(format!("Invalid JSON webhook body: {e}")))?;
+        let mut fields = HashMap::new();
+        if let Value::Object(map) = &value {
+            for (k, v) in map {
+                let field = match v {
+                    Value::String(s) => s.clone(),
+                    Value::Null => continue,
+                    other => other.to_string(),
+                };
+                fields.insert(k.clone(), field);
+            }
+        }
+        Ok((fields, value))
+    } else {
+        let fields: HashMap<String, String> = url::form_urlencoded::parse(raw.as_bytes())
+            .into_owned()
+            .collect();
+        let value = serde_json::to_value(&fields).unwrap_or(Value::Null);
+        Ok((fields, value))
+    }
+}
+
+fn webhook_status_label(raw_status: &str) -> String {
+    let status = raw_status.to_ascii_lowercase();
+    if (status.contains("valid") && !status.contains("invalid"))
+        || status.contains("success")
+        || status.contains("paid")
+    {
+        "paid".to_owned()
+    } else if status.contains("pending") {
+        "pending".to_owned()
+    } else if status.contains("cancel") {
+        "cancelled".to_owned()
+    } else if status.contains("fail") || status.contains("invalid") {
+        "failed".to_owned()
+    } else {
+        status
+    }
+}
+
 fn parse_environment(raw: EnvInput) -> PyResult<Environment> {
     parse_environment_raw(raw).map_err(PyValueError::new_err)
 }
@@ -131,14 +357,15 @@ fn parse_environment_raw(raw: EnvInput) -> std::result::Result<Environment, Stri
 
 fn parse_http_settings(
     raw: Option<HttpSettingsInput>,
-) -> PyResult<bd_payment_gateway_core::HttpSettings> {
+) -> PyResult<(bd_payment_gateway_core::HttpSettings, Duration)> {
     parse_http_settings_raw(raw).map_err(PyValueError::new_err)
 }
 
 fn parse_http_settings_raw(
     raw: Option<HttpSettingsInput>,
-) -> std::result::Result<bd_payment_gateway_core::HttpSettings, String> {
+) -> std::result::Result<(bd_payment_gateway_core::HttpSettings, Duration), String> {
     let mut settings = bd_payment_gateway_core::HttpSettings::default();
+    let mut idempotency_ttl = DEFAULT_IDEMPOTENCY_TTL;
     if let Some(raw) = raw {
         if let Some(timeout_ms) = raw.timeout_ms {
             settings.timeout = Duration::from_millis(timeout_ms);
@@ -158,6 +385,9 @@ fn parse_http_settings_raw(
             }
             settings.user_agent = user_agent;
         }
+        if let Some(idempotency_ttl_ms) = raw.idempotency_ttl_ms {
+            idempotency_ttl = Duration::from_millis(idempotency_ttl_ms);
+        }
     }
 
     if settings.initial_backoff > settings.max_backoff {
@@ -167,10 +397,10 @@ fn parse_http_settings_raw(
         );
     }
 
-    Ok(settings)
+    Ok((settings, idempotency_ttl))
 }
 
-fn to_py_err(err: BdPaymentError) -> PyErr {
+fn to_py_err(err: BdPaymentError, locale: Locale) -> PyErr {
     let (code, message, hint, provider_payload) = match &err {
         BdPaymentError::ConfigError {
             code,
@@ -191,12 +421,51 @@ fn to_py_err(err: BdPaymentError) -> PyErr {
             code,
             message,
             hint,
+        }
+        | BdPaymentError::Timeout {
+            code,
+            message,
+            hint,
         } => (
             code.as_str().to_owned(),
             message.clone(),
             hint.clone(),
             None::<Value>,
         ),
+        BdPaymentError::TimeoutError {
+            code,
+            message,
+            hint,
+            attempts,
+        } => (
+            code.as_str().to_owned(),
+            message.clone(),
+            hint.clone(),
+            Some(json!({ "attempts": attempts })),
+        ),
+        BdPaymentError::PluginError {
+            code,
+            message,
+            hint,
+            module,
+            operation,
+        } => (
+            code.as_str().to_owned(),
+            message.clone(),
+            hint.clone(),
+            Some(json!({ "module": module, "operation": operation })),
+        ),
+        BdPaymentError::RateLimited {
+            code,
+            message,
+            hint,
+            retry_after,
+        } => (
+            code.as_str().to_owned(),
+            message.clone(),
+            hint.clone(),
+            Some(json!({ "retry_after_ms": retry_after.as_ref().map(Duration::as_millis) })),
+        ),
         BdPaymentError::HttpError {
             code,
             message,
@@ -204,6 +473,7 @@ fn to_py_err(err: BdPaymentError) -> PyErr {
             status,
             request_id,
             body,
+            attempt,
         } => (
             code.as_str().to_owned(),
             message.clone(),
@@ -212,6 +482,7 @@ fn to_py_err(err: BdPaymentError) -> PyErr {
                 "status": status,
                 "request_id": request_id,
                 "body": body,
+                "attempt": attempt,
             })),
         ),
         BdPaymentError::ProviderError {
@@ -220,6 +491,10 @@ fn to_py_err(err: BdPaymentError) -> PyErr {
             hint,
             provider_code,
             request_id,
+            debug_id,
+            help_links,
+            error_type,
+            ..
         } => (
             code.as_str().to_owned(),
             message.clone(),
@@ -227,9 +502,32 @@ fn to_py_err(err: BdPaymentError) -> PyErr {
             Some(json!({
                 "provider_code": provider_code,
                 "request_id": request_id,
+                "debug_id": debug_id,
+                "help_links": help_links.iter().map(Url::to_string).collect::<Vec<_>>(),
+                "error_type": error_type.as_ref().map(|t| format!("{t:?}")),
             })),
         ),
+        BdPaymentError::Transport(source) => (
+            err.code().as_str().to_owned(),
+            source.to_string(),
+            err.hint().to_owned(),
+            None::<Value>,
+        ),
+        BdPaymentError::Json(source) => (
+            err.code().as_str().to_owned(),
+            source.to_string(),
+            err.hint().to_owned(),
+            None::<Value>,
+        ),
+        BdPaymentError::UrlParse(source) => (
+            err.code().as_str().to_owned(),
+            source.to_string(),
+            err.hint().to_owned(),
+            None::<Value>,
+        ),
     };
+    let message = localized_message(&message, locale);
+    let hint = localized_hint(&code, &hint, locale);
 
     let fallback_payload = json!({
         "message": message,
@@ -284,18 +582,22 @@ fn map_initiate_response(
     }
 }
 
+fn payment_status_label(status: bd_payment_gateway_core::PaymentStatus) -> String {
+    match status {
+        bd_payment_gateway_core::PaymentStatus::Pending => "pending".to_owned(),
+        bd_payment_gateway_core::PaymentStatus::Paid => "paid".to_owned(),
+        bd_payment_gateway_core::PaymentStatus::Failed => "failed".to_owned(),
+        bd_payment_gateway_core::PaymentStatus::Cancelled => "cancelled".to_owned(),
+        bd_payment_gateway_core::PaymentStatus::Refunded => "refunded".to_owned(),
+        bd_payment_gateway_core::PaymentStatus::Unknown(v) => v,
+    }
+}
+
 fn map_verify_response(
     resp: bd_payment_gateway_core::VerifyPaymentResponse,
 ) -> VerifyPaymentResponse {
     VerifyPaymentResponse {
-        status: match resp.status {
-            bd_payment_gateway_core::PaymentStatus::Pending => "pending".to_owned(),
-            bd_payment_gateway_core::PaymentStatus::Paid => "paid".to_owned(),
-            bd_payment_gateway_core::PaymentStatus::Failed => "failed".to_owned(),
-            bd_payment_gateway_core::PaymentStatus::Cancelled => "cancelled".to_owned(),
-            bd_payment_gateway_core::PaymentStatus::Refunded => "refunded".to_owned(),
-            bd_payment_gateway_core::PaymentStatus::Unknown(v) => v,
-        },
+        status: payment_status_label(resp.status),
         provider_reference: resp.provider_reference,
         amount: resp.amount.map(|a| a.to_string()),
         currency: resp.currency.map(|c| c.as_code().to_owned()),
@@ -304,6 +606,64 @@ fn map_verify_response(
     }
 }
 
+/// Retry budget for [`verify_payment_until_settled`]-style polling, parsed from either
+/// `{"attempts": N}` (a fixed number of tries) or `{"timeout_ms": T}` (an overall wall-clock
+/// budget), mirroring the `Retry` strategy rust-lightning uses to decide "is it still worth
+/// trying again".
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RetryInput {
+    Attempts { attempts: u32 },
+    TimeoutMs { timeout_ms: u64 },
+}
+
+fn is_terminal_status(status: &str) -> bool {
+    matches!(status, "paid" | "failed" | "cancelled" | "refunded")
+}
+
+/// Repeatedly calls `verify` until the mapped status is terminal or the retry budget from
+/// `retry` is exhausted, sleeping between attempts with exponential backoff bounded by
+/// `initial_backoff`/`max_backoff`. Returns the last response seen either way.
+async fn poll_until_settled<F, Fut>(
+    retry: RetryInput,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    mut verify: F,
+) -> PyResult<VerifyPaymentResponse>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = PyResult<VerifyPaymentResponse>>,
+{
+    let deadline = match retry {
+        RetryInput::TimeoutMs { timeout_ms } => {
+            Some(tokio::time::Instant::now() + Duration::from_millis(timeout_ms))
+        }
+        RetryInput::Attempts { .. } => None,
+    };
+    let max_attempts = match retry {
+        RetryInput::Attempts { attempts } => attempts.max(1),
+        RetryInput::TimeoutMs { .. } => u32::MAX,
+    };
+
+    let mut delay = initial_backoff;
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let resp = verify().await?;
+        if is_terminal_status(&resp.status) || attempt >= max_attempts {
+            return Ok(resp);
+        }
+        if let Some(deadline) = deadline {
+            if tokio::time::Instant::now() >= deadline {
+                return Ok(resp);
+            }
+        }
+
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(max_backoff);
+    }
+}
+
 fn map_refund_response(resp: bd_payment_gateway_core::RefundResponse) -> RefundResponse {
     RefundResponse {
         status: match resp.status {
@@ -326,12 +686,18 @@ struct ShurjopayConfigInput {
     prefix: String,
     environment: EnvInput,
     http_settings: Option<HttpSettingsInput>,
+    /// `"en"` (default) or `"bn"`; controls the language of translated error hints.
+    locale: Option<String>,
 }
 
 #[cfg(feature = "shurjopay")]
 #[pyclass]
 struct ShurjopayClient {
     inner: bd_payment_gateway_shurjopay::ShurjopayClient,
+    idempotency_ttl: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    locale: Locale,
 }
 
 #[cfg(feature = "shurjopay")]
@@ -340,26 +706,85 @@ impl ShurjopayClient {
     #[new]
     fn new(config: &Bound<'_, PyAny>) -> PyResult<Self> {
         let cfg: ShurjopayConfigInput = parse_json_input(config, "shurjoPay config")?;
+        let (http_settings, idempotency_ttl) = parse_http_settings(cfg.http_settings)?;
+        let initial_backoff = http_settings.initial_backoff;
+        let max_backoff = http_settings.max_backoff;
+        let locale = parse_locale(cfg.locale, "shurjoPay config")?;
         let config = bd_payment_gateway_shurjopay::Config {
             username: cfg.username,
             password: SecretString::new(cfg.password.into()),
             prefix: cfg.prefix,
             environment: parse_environment(cfg.environment)?,
-            http_settings: parse_http_settings(cfg.http_settings)?,
+            http_settings,
         };
-        let inner =
-            bd_payment_gateway_shurjopay::ShurjopayClient::new(config).map_err(to_py_err)?;
-        Ok(Self { inner })
+        let inner = bd_payment_gateway_shurjopay::ShurjopayClient::new(config)
+            .map_err(|e| to_py_err(e, locale))?;
+        Ok(Self {
+            inner,
+            idempotency_ttl,
+            initial_backoff,
+            max_backoff,
+            locale,
+        })
     }
 
     fn initiate_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<InitiatePaymentResponse> {
-        let request: bd_payment_gateway_shurjopay::InitiatePaymentRequest =
-            parse_json_input(request, "shurjoPay initiate request")?;
+        let (request, idempotency_key): (bd_payment_gateway_shurjopay::InitiatePaymentRequest, _) =
+            parse_initiate_request(request, "shurjoPay initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("shurjopay", key, self.idempotency_ttl)? {
+                return Ok(cached);
+            }
+        }
 
-        let resp = RUNTIME
-            .block_on(self.inner.initiate_payment(&request))
-            .map_err(to_py_err)?;
-        Ok(map_initiate_response(resp))
+        let resp = match RUNTIME.block_on(self.inner.initiate_payment(&request)) {
+            Ok(resp) => resp,
+            Err(e) => {
+                if let Some(key) = &idempotency_key {
+                    release_idempotent_claim("shurjopay", key);
+                }
+                return Err(to_py_err(e, self.locale));
+            }
+        };
+        let mapped = map_initiate_response(resp);
+        if let Some(key) = &idempotency_key {
+            store_idempotent_response("shurjopay", key, &mapped);
+        }
+        Ok(mapped)
+    }
+
+    /// Non-blocking counterpart of `initiate_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn initiate_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (request, idempotency_key): (bd_payment_gateway_shurjopay::InitiatePaymentRequest, _) =
+            parse_initiate_request(request, "shurjoPay initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("shurjopay", key, self.idempotency_ttl)? {
+                return pyo3_asyncio::tokio::future_into_py(py, async move { Ok(cached) });
+            }
+        }
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = match inner.initiate_payment(&request).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    if let Some(key) = &idempotency_key {
+                        release_idempotent_claim("shurjopay", key);
+                    }
+                    return Err(to_py_err(e, locale));
+                }
+            };
+            let mapped = map_initiate_response(resp);
+            if let Some(key) = &idempotency_key {
+                store_idempotent_response("shurjopay", key, &mapped);
+            }
+            Ok(mapped)
+        })
     }
 
     fn verify_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<VerifyPaymentResponse> {
@@ -368,9 +793,115 @@ impl ShurjopayClient {
 
         let resp = RUNTIME
             .block_on(self.inner.verify_payment(&request))
-            .map_err(to_py_err)?;
+            .map_err(|e| to_py_err(e, self.locale))?;
         Ok(map_verify_response(resp))
     }
+
+    /// Non-blocking counterpart of `verify_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_shurjopay::VerifyPaymentRequest =
+            parse_json_input(request, "shurjoPay verify request")?;
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+            Ok(map_verify_response(resp))
+        })
+    }
+
+    /// Polls `verify_payment` until the status is terminal (`paid`/`failed`/`cancelled`/
+    /// `refunded`) or `retry` (`{"attempts": N}` or `{"timeout_ms": T}`) is exhausted, backing
+    /// off exponentially between attempts within `http_settings.initial_backoff_ms`/
+    /// `max_backoff_ms`. Returns the last response seen, settled or not.
+    fn verify_payment_until_settled(
+        &self,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<VerifyPaymentResponse> {
+        let request: bd_payment_gateway_shurjopay::VerifyPaymentRequest =
+            parse_json_input(request, "shurjoPay verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        RUNTIME.block_on(poll_until_settled(
+            retry,
+            self.initial_backoff,
+            self.max_backoff,
+            || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, self.locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            },
+        ))
+    }
+
+    /// Non-blocking counterpart of `verify_payment_until_settled`, awaitable from an `asyncio`
+    /// event loop instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_until_settled_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_shurjopay::VerifyPaymentRequest =
+            parse_json_input(request, "shurjoPay verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        let initial_backoff = self.initial_backoff;
+        let max_backoff = self.max_backoff;
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            poll_until_settled(retry, initial_backoff, max_backoff, || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            })
+            .await
+        })
+    }
+
+    /// Parses a shurjoPay IPN callback into a [`WebhookEvent`]. shurjoPay does not publish a
+    /// callback signature scheme, so `verified` is always `false`; call `verify_payment` with
+    /// the callback's `sp_order_id` to authoritatively confirm the transaction.
+    #[pyo3(signature = (headers, body))]
+    fn parse_webhook(
+        &self,
+        headers: &Bound<'_, PyAny>,
+        body: &Bound<'_, PyAny>,
+    ) -> PyResult<WebhookEvent> {
+        let _ = headers;
+        let (fields, raw) = decode_webhook_body(body)?;
+
+        let status_raw = fields
+            .get("sp_message")
+            .or_else(|| fields.get("status"))
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_owned());
+        let provider_reference = fields
+            .get("sp_order_id")
+            .or_else(|| fields.get("order_id"))
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(WebhookEvent {
+            status: webhook_status_label(&status_raw),
+            provider_reference,
+            amount: fields.get("amount").cloned(),
+            currency: fields.get("currency").cloned(),
+            verified: false,
+            raw: raw.to_string(),
+        })
+    }
 }
 
 #[cfg(feature = "portwallet")]
@@ -380,12 +911,18 @@ struct PortwalletConfigInput {
     app_secret: String,
     environment: EnvInput,
     http_settings: Option<HttpSettingsInput>,
+    /// `"en"` (default) or `"bn"`; controls the language of translated error hints.
+    locale: Option<String>,
 }
 
 #[cfg(feature = "portwallet")]
 #[pyclass]
 struct PortwalletClient {
     inner: bd_payment_gateway_portwallet::PortwalletClient,
+    idempotency_ttl: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    locale: Locale,
 }
 
 #[cfg(feature = "portwallet")]
@@ -394,24 +931,87 @@ impl PortwalletClient {
     #[new]
     fn new(config: &Bound<'_, PyAny>) -> PyResult<Self> {
         let cfg: PortwalletConfigInput = parse_json_input(config, "PortWallet config")?;
+        let (http_settings, idempotency_ttl) = parse_http_settings(cfg.http_settings)?;
+        let initial_backoff = http_settings.initial_backoff;
+        let max_backoff = http_settings.max_backoff;
+        let locale = parse_locale(cfg.locale, "PortWallet config")?;
         let config = bd_payment_gateway_portwallet::Config {
             app_key: cfg.app_key,
             app_secret: SecretString::new(cfg.app_secret.into()),
             environment: parse_environment(cfg.environment)?,
-            http_settings: parse_http_settings(cfg.http_settings)?,
+            http_settings,
         };
-        let inner =
-            bd_payment_gateway_portwallet::PortwalletClient::new(config).map_err(to_py_err)?;
-        Ok(Self { inner })
+        let inner = bd_payment_gateway_portwallet::PortwalletClient::new(config)
+            .map_err(|e| to_py_err(e, locale))?;
+        Ok(Self {
+            inner,
+            idempotency_ttl,
+            initial_backoff,
+            max_backoff,
+            locale,
+        })
     }
 
     fn initiate_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<InitiatePaymentResponse> {
-        let request: bd_payment_gateway_portwallet::InitiatePaymentRequest =
-            parse_json_input(request, "PortWallet initiate request")?;
-        let resp = RUNTIME
-            .block_on(self.inner.initiate_payment(&request))
-            .map_err(to_py_err)?;
-        Ok(map_initiate_response(resp))
+        let (request, idempotency_key): (
+            bd_payment_gateway_portwallet::InitiatePaymentRequest,
+            _,
+        ) = parse_initiate_request(request, "PortWallet initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("portwallet", key, self.idempotency_ttl)? {
+                return Ok(cached);
+            }
+        }
+        let resp = match RUNTIME.block_on(self.inner.initiate_payment(&request)) {
+            Ok(resp) => resp,
+            Err(e) => {
+                if let Some(key) = &idempotency_key {
+                    release_idempotent_claim("portwallet", key);
+                }
+                return Err(to_py_err(e, self.locale));
+            }
+        };
+        let mapped = map_initiate_response(resp);
+        if let Some(key) = &idempotency_key {
+            store_idempotent_response("portwallet", key, &mapped);
+        }
+        Ok(mapped)
+    }
+
+    /// Non-blocking counterpart of `initiate_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn initiate_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (request, idempotency_key): (
+            bd_payment_gateway_portwallet::InitiatePaymentRequest,
+            _,
+        ) = parse_initiate_request(request, "PortWallet initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("portwallet", key, self.idempotency_ttl)? {
+                return pyo3_asyncio::tokio::future_into_py(py, async move { Ok(cached) });
+            }
+        }
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = match inner.initiate_payment(&request).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    if let Some(key) = &idempotency_key {
+                        release_idempotent_claim("portwallet", key);
+                    }
+                    return Err(to_py_err(e, locale));
+                }
+            };
+            let mapped = map_initiate_response(resp);
+            if let Some(key) = &idempotency_key {
+                store_idempotent_response("portwallet", key, &mapped);
+            }
+            Ok(mapped)
+        })
     }
 
     fn verify_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<VerifyPaymentResponse> {
@@ -419,18 +1019,137 @@ impl PortwalletClient {
             parse_json_input(request, "PortWallet verify request")?;
         let resp = RUNTIME
             .block_on(self.inner.verify_payment(&request))
-            .map_err(to_py_err)?;
+            .map_err(|e| to_py_err(e, self.locale))?;
         Ok(map_verify_response(resp))
     }
 
+    /// Non-blocking counterpart of `verify_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_portwallet::VerifyPaymentRequest =
+            parse_json_input(request, "PortWallet verify request")?;
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+            Ok(map_verify_response(resp))
+        })
+    }
+
+    /// Polls `verify_payment` until the status is terminal (`paid`/`failed`/`cancelled`/
+    /// `refunded`) or `retry` (`{"attempts": N}` or `{"timeout_ms": T}`) is exhausted, backing
+    /// off exponentially between attempts within `http_settings.initial_backoff_ms`/
+    /// `max_backoff_ms`. Returns the last response seen, settled or not.
+    fn verify_payment_until_settled(
+        &self,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<VerifyPaymentResponse> {
+        let request: bd_payment_gateway_portwallet::VerifyPaymentRequest =
+            parse_json_input(request, "PortWallet verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        RUNTIME.block_on(poll_until_settled(
+            retry,
+            self.initial_backoff,
+            self.max_backoff,
+            || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, self.locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            },
+        ))
+    }
+
+    /// Non-blocking counterpart of `verify_payment_until_settled`, awaitable from an `asyncio`
+    /// event loop instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_until_settled_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_portwallet::VerifyPaymentRequest =
+            parse_json_input(request, "PortWallet verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        let initial_backoff = self.initial_backoff;
+        let max_backoff = self.max_backoff;
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            poll_until_settled(retry, initial_backoff, max_backoff, || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            })
+            .await
+        })
+    }
+
     fn refund(&self, request: &Bound<'_, PyAny>) -> PyResult<RefundResponse> {
         let request: bd_payment_gateway_portwallet::RefundRequest =
             parse_json_input(request, "PortWallet refund request")?;
         let resp = RUNTIME
             .block_on(self.inner.refund(&request))
-            .map_err(to_py_err)?;
+            .map_err(|e| to_py_err(e, self.locale))?;
         Ok(map_refund_response(resp))
     }
+
+    /// Non-blocking counterpart of `refund`, awaitable from an `asyncio` event loop instead of
+    /// blocking the calling thread on the shared tokio runtime.
+    fn refund_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_portwallet::RefundRequest =
+            parse_json_input(request, "PortWallet refund request")?;
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = inner.refund(&request).await.map_err(|e| to_py_err(e, locale))?;
+            Ok(map_refund_response(resp))
+        })
+    }
+
+    /// Parses a PortWallet IPN callback into a [`WebhookEvent`]. `headers` is ignored, so
+    /// PortWallet's `x-app-signature`/`x-app-timestamp` headers are not checked here and
+    /// `verified` is always `false`; call `verify_payment` with
+    /// the callback's `invoice_id` to authoritatively confirm the transaction.
+    #[pyo3(signature = (headers, body))]
+    fn parse_webhook(
+        &self,
+        headers: &Bound<'_, PyAny>,
+        body: &Bound<'_, PyAny>,
+    ) -> PyResult<WebhookEvent> {
+        let _ = headers;
+        let (fields, raw) = decode_webhook_body(body)?;
+
+        let status_raw = fields
+            .get("status")
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_owned());
+        let provider_reference = fields.get("invoice_id").cloned().unwrap_or_default();
+
+        Ok(WebhookEvent {
+            status: webhook_status_label(&status_raw),
+            provider_reference,
+            amount: fields.get("amount").cloned(),
+            currency: fields.get("currency").cloned(),
+            verified: false,
+            raw: raw.to_string(),
+        })
+    }
 }
 
 #[cfg(feature = "aamarpay")]
@@ -440,12 +1159,18 @@ struct AamarpayConfigInput {
     signature_key: String,
     environment: EnvInput,
     http_settings: Option<HttpSettingsInput>,
+    /// `"en"` (default) or `"bn"`; controls the language of translated error hints.
+    locale: Option<String>,
 }
 
 #[cfg(feature = "aamarpay")]
 #[pyclass]
 struct AamarpayClient {
     inner: bd_payment_gateway_aamarpay::AamarpayClient,
+    idempotency_ttl: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    locale: Locale,
 }
 
 #[cfg(feature = "aamarpay")]
@@ -454,23 +1179,84 @@ impl AamarpayClient {
     #[new]
     fn new(config: &Bound<'_, PyAny>) -> PyResult<Self> {
         let cfg: AamarpayConfigInput = parse_json_input(config, "aamarPay config")?;
+        let (http_settings, idempotency_ttl) = parse_http_settings(cfg.http_settings)?;
+        let initial_backoff = http_settings.initial_backoff;
+        let max_backoff = http_settings.max_backoff;
+        let locale = parse_locale(cfg.locale, "aamarPay config")?;
         let config = bd_payment_gateway_aamarpay::Config {
             store_id: cfg.store_id,
             signature_key: SecretString::new(cfg.signature_key.into()),
             environment: parse_environment(cfg.environment)?,
-            http_settings: parse_http_settings(cfg.http_settings)?,
+            http_settings,
+            refund_endpoint: None,
         };
-        let inner = bd_payment_gateway_aamarpay::AamarpayClient::new(config).map_err(to_py_err)?;
-        Ok(Self { inner })
+        let inner = bd_payment_gateway_aamarpay::AamarpayClient::new(config)
+            .map_err(|e| to_py_err(e, locale))?;
+        Ok(Self {
+            inner,
+            idempotency_ttl,
+            initial_backoff,
+            max_backoff,
+            locale,
+        })
     }
 
     fn initiate_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<InitiatePaymentResponse> {
-        let request: bd_payment_gateway_aamarpay::InitiatePaymentRequest =
-            parse_json_input(request, "aamarPay initiate request")?;
-        let resp = RUNTIME
-            .block_on(self.inner.initiate_payment(&request))
-            .map_err(to_py_err)?;
-        Ok(map_initiate_response(resp))
+        let (request, idempotency_key): (bd_payment_gateway_aamarpay::InitiatePaymentRequest, _) =
+            parse_initiate_request(request, "aamarPay initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("aamarpay", key, self.idempotency_ttl)? {
+                return Ok(cached);
+            }
+        }
+        let resp = match RUNTIME.block_on(self.inner.initiate_payment(&request)) {
+            Ok(resp) => resp,
+            Err(e) => {
+                if let Some(key) = &idempotency_key {
+                    release_idempotent_claim("aamarpay", key);
+                }
+                return Err(to_py_err(e, self.locale));
+            }
+        };
+        let mapped = map_initiate_response(resp);
+        if let Some(key) = &idempotency_key {
+            store_idempotent_response("aamarpay", key, &mapped);
+        }
+        Ok(mapped)
+    }
+
+    /// Non-blocking counterpart of `initiate_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn initiate_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (request, idempotency_key): (bd_payment_gateway_aamarpay::InitiatePaymentRequest, _) =
+            parse_initiate_request(request, "aamarPay initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("aamarpay", key, self.idempotency_ttl)? {
+                return pyo3_asyncio::tokio::future_into_py(py, async move { Ok(cached) });
+            }
+        }
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = match inner.initiate_payment(&request).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    if let Some(key) = &idempotency_key {
+                        release_idempotent_claim("aamarpay", key);
+                    }
+                    return Err(to_py_err(e, locale));
+                }
+            };
+            let mapped = map_initiate_response(resp);
+            if let Some(key) = &idempotency_key {
+                store_idempotent_response("aamarpay", key, &mapped);
+            }
+            Ok(mapped)
+        })
     }
 
     fn verify_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<VerifyPaymentResponse> {
@@ -478,9 +1264,110 @@ impl AamarpayClient {
             parse_json_input(request, "aamarPay verify request")?;
         let resp = RUNTIME
             .block_on(self.inner.verify_payment(&request))
-            .map_err(to_py_err)?;
+            .map_err(|e| to_py_err(e, self.locale))?;
         Ok(map_verify_response(resp))
     }
+
+    /// Non-blocking counterpart of `verify_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_aamarpay::VerifyPaymentRequest =
+            parse_json_input(request, "aamarPay verify request")?;
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+            Ok(map_verify_response(resp))
+        })
+    }
+
+    /// Polls `verify_payment` until the status is terminal (`paid`/`failed`/`cancelled`/
+    /// `refunded`) or `retry` (`{"attempts": N}` or `{"timeout_ms": T}`) is exhausted, backing
+    /// off exponentially between attempts within `http_settings.initial_backoff_ms`/
+    /// `max_backoff_ms`. Returns the last response seen, settled or not.
+    fn verify_payment_until_settled(
+        &self,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<VerifyPaymentResponse> {
+        let request: bd_payment_gateway_aamarpay::VerifyPaymentRequest =
+            parse_json_input(request, "aamarPay verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        RUNTIME.block_on(poll_until_settled(
+            retry,
+            self.initial_backoff,
+            self.max_backoff,
+            || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, self.locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            },
+        ))
+    }
+
+    /// Non-blocking counterpart of `verify_payment_until_settled`, awaitable from an `asyncio`
+    /// event loop instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_until_settled_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_aamarpay::VerifyPaymentRequest =
+            parse_json_input(request, "aamarPay verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        let initial_backoff = self.initial_backoff;
+        let max_backoff = self.max_backoff;
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            poll_until_settled(retry, initial_backoff, max_backoff, || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            })
+            .await
+        })
+    }
+
+    /// Parses an aamarPay IPN callback into a [`WebhookEvent`]. aamarPay does not document a
+    /// callback signature, so instead of trusting the posted `pay_status` this delegates to
+    /// `AamarpayClient::verify_callback`, which checks `store_id` and re-fetches
+    /// `/api/v1/trxcheck/request.php` before reporting `Paid` -- a stronger guarantee than a
+    /// local signature check, so `verified` is always `true`.
+    #[pyo3(signature = (headers, body))]
+    fn parse_webhook(
+        &self,
+        headers: &Bound<'_, PyAny>,
+        body: &Bound<'_, PyAny>,
+    ) -> PyResult<WebhookEvent> {
+        let _ = headers;
+        let (fields, _raw) = decode_webhook_body(body)?;
+
+        let resp = RUNTIME
+            .block_on(self.inner.verify_callback(&fields))
+            .map_err(|e| to_py_err(e, self.locale))?;
+
+        Ok(WebhookEvent {
+            status: payment_status_label(resp.status),
+            provider_reference: resp.provider_reference,
+            amount: resp.amount.map(|a| a.to_string()),
+            currency: resp.currency.map(|c| c.as_code().to_owned()),
+            verified: true,
+            raw: resp.raw.to_string(),
+        })
+    }
 }
 
 #[cfg(feature = "sslcommerz")]
@@ -490,12 +1377,18 @@ struct SslcommerzConfigInput {
     store_passwd: String,
     environment: EnvInput,
     http_settings: Option<HttpSettingsInput>,
+    /// `"en"` (default) or `"bn"`; controls the language of translated error hints.
+    locale: Option<String>,
 }
 
 #[cfg(feature = "sslcommerz")]
 #[pyclass]
 struct SslcommerzClient {
     inner: bd_payment_gateway_sslcommerz::SslcommerzClient,
+    idempotency_ttl: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    locale: Locale,
 }
 
 #[cfg(feature = "sslcommerz")]
@@ -504,24 +1397,87 @@ impl SslcommerzClient {
     #[new]
     fn new(config: &Bound<'_, PyAny>) -> PyResult<Self> {
         let cfg: SslcommerzConfigInput = parse_json_input(config, "SSLCOMMERZ config")?;
+        let (http_settings, idempotency_ttl) = parse_http_settings(cfg.http_settings)?;
+        let initial_backoff = http_settings.initial_backoff;
+        let max_backoff = http_settings.max_backoff;
+        let locale = parse_locale(cfg.locale, "SSLCOMMERZ config")?;
         let config = bd_payment_gateway_sslcommerz::Config {
             store_id: cfg.store_id,
             store_passwd: SecretString::new(cfg.store_passwd.into()),
             environment: parse_environment(cfg.environment)?,
-            http_settings: parse_http_settings(cfg.http_settings)?,
+            http_settings,
         };
-        let inner =
-            bd_payment_gateway_sslcommerz::SslcommerzClient::new(config).map_err(to_py_err)?;
-        Ok(Self { inner })
+        let inner = bd_payment_gateway_sslcommerz::SslcommerzClient::new(config)
+            .map_err(|e| to_py_err(e, locale))?;
+        Ok(Self {
+            inner,
+            idempotency_ttl,
+            initial_backoff,
+            max_backoff,
+            locale,
+        })
     }
 
     fn initiate_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<InitiatePaymentResponse> {
-        let request: bd_payment_gateway_sslcommerz::InitiatePaymentRequest =
-            parse_json_input(request, "SSLCOMMERZ initiate request")?;
-        let resp = RUNTIME
-            .block_on(self.inner.initiate_payment(&request))
-            .map_err(to_py_err)?;
-        Ok(map_initiate_response(resp))
+        let (request, idempotency_key): (
+            bd_payment_gateway_sslcommerz::InitiatePaymentRequest,
+            _,
+        ) = parse_initiate_request(request, "SSLCOMMERZ initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("sslcommerz", key, self.idempotency_ttl)? {
+                return Ok(cached);
+            }
+        }
+        let resp = match RUNTIME.block_on(self.inner.initiate_payment(&request)) {
+            Ok(resp) => resp,
+            Err(e) => {
+                if let Some(key) = &idempotency_key {
+                    release_idempotent_claim("sslcommerz", key);
+                }
+                return Err(to_py_err(e, self.locale));
+            }
+        };
+        let mapped = map_initiate_response(resp);
+        if let Some(key) = &idempotency_key {
+            store_idempotent_response("sslcommerz", key, &mapped);
+        }
+        Ok(mapped)
+    }
+
+    /// Non-blocking counterpart of `initiate_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn initiate_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (request, idempotency_key): (
+            bd_payment_gateway_sslcommerz::InitiatePaymentRequest,
+            _,
+        ) = parse_initiate_request(request, "SSLCOMMERZ initiate request")?;
+        if let Some(key) = &idempotency_key {
+            if let Some(cached) = claim_idempotent_request("sslcommerz", key, self.idempotency_ttl)? {
+                return pyo3_asyncio::tokio::future_into_py(py, async move { Ok(cached) });
+            }
+        }
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = match inner.initiate_payment(&request).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    if let Some(key) = &idempotency_key {
+                        release_idempotent_claim("sslcommerz", key);
+                    }
+                    return Err(to_py_err(e, locale));
+                }
+            };
+            let mapped = map_initiate_response(resp);
+            if let Some(key) = &idempotency_key {
+                store_idempotent_response("sslcommerz", key, &mapped);
+            }
+            Ok(mapped)
+        })
     }
 
     fn verify_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<VerifyPaymentResponse> {
@@ -529,18 +1485,373 @@ impl SslcommerzClient {
             parse_json_input(request, "SSLCOMMERZ verify request")?;
         let resp = RUNTIME
             .block_on(self.inner.verify_payment(&request))
-            .map_err(to_py_err)?;
+            .map_err(|e| to_py_err(e, self.locale))?;
         Ok(map_verify_response(resp))
     }
 
+    /// Non-blocking counterpart of `verify_payment`, awaitable from an `asyncio` event loop
+    /// instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_sslcommerz::VerifyPaymentRequest =
+            parse_json_input(request, "SSLCOMMERZ verify request")?;
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+            Ok(map_verify_response(resp))
+        })
+    }
+
+    /// Polls `verify_payment` until the status is terminal (`paid`/`failed`/`cancelled`/
+    /// `refunded`) or `retry` (`{"attempts": N}` or `{"timeout_ms": T}`) is exhausted, backing
+    /// off exponentially between attempts within `http_settings.initial_backoff_ms`/
+    /// `max_backoff_ms`. Returns the last response seen, settled or not.
+    fn verify_payment_until_settled(
+        &self,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<VerifyPaymentResponse> {
+        let request: bd_payment_gateway_sslcommerz::VerifyPaymentRequest =
+            parse_json_input(request, "SSLCOMMERZ verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        RUNTIME.block_on(poll_until_settled(
+            retry,
+            self.initial_backoff,
+            self.max_backoff,
+            || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, self.locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            },
+        ))
+    }
+
+    /// Non-blocking counterpart of `verify_payment_until_settled`, awaitable from an `asyncio`
+    /// event loop instead of blocking the calling thread on the shared tokio runtime.
+    fn verify_payment_until_settled_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+        retry: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_sslcommerz::VerifyPaymentRequest =
+            parse_json_input(request, "SSLCOMMERZ verify request")?;
+        let retry: RetryInput = parse_json_input(retry, "retry policy")?;
+        let inner = self.inner.clone();
+        let initial_backoff = self.initial_backoff;
+        let max_backoff = self.max_backoff;
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            poll_until_settled(retry, initial_backoff, max_backoff, || {
+                let inner = inner.clone();
+                let request = request.clone();
+                async move {
+                    let resp = inner.verify_payment(&request).await.map_err(|e| to_py_err(e, locale))?;
+                    Ok(map_verify_response(resp))
+                }
+            })
+            .await
+        })
+    }
+
     fn refund(&self, request: &Bound<'_, PyAny>) -> PyResult<RefundResponse> {
         let request: bd_payment_gateway_sslcommerz::RefundRequest =
             parse_json_input(request, "SSLCOMMERZ refund request")?;
         let resp = RUNTIME
             .block_on(self.inner.refund(&request))
-            .map_err(to_py_err)?;
+            .map_err(|e| to_py_err(e, self.locale))?;
         Ok(map_refund_response(resp))
     }
+
+    /// Non-blocking counterpart of `refund`, awaitable from an `asyncio` event loop instead of
+    /// blocking the calling thread on the shared tokio runtime.
+    fn refund_async<'p>(
+        &self,
+        py: Python<'p>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request: bd_payment_gateway_sslcommerz::RefundRequest =
+            parse_json_input(request, "SSLCOMMERZ refund request")?;
+        let inner = self.inner.clone();
+        let locale = self.locale;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let resp = inner.refund(&request).await.map_err(|e| to_py_err(e, locale))?;
+            Ok(map_refund_response(resp))
+        })
+    }
+
+    /// Parses an SSLCOMMERZ IPN callback into a [`WebhookEvent`], rejecting it with a
+    /// `ValidationError` unless the recomputed `verify_sign` MD5 matches the one SSLCOMMERZ sent.
+    #[pyo3(signature = (headers, body))]
+    fn parse_webhook(
+        &self,
+        headers: &Bound<'_, PyAny>,
+        body: &Bound<'_, PyAny>,
+    ) -> PyResult<WebhookEvent> {
+        let _ = headers;
+        let (fields, raw) = decode_webhook_body(body)?;
+
+        if !self.inner.verify_ipn_signature(&fields) {
+            return Err(to_py_err(
+                BdPaymentError::validation(
+                    "SSLCOMMERZ webhook verify_sign did not match the recomputed signature.",
+                    "Reject this callback; it may be forged, replayed, or store_passwd is misconfigured.",
+                ),
+                self.locale,
+            ));
+        }
+
+        let status_raw = fields
+            .get("status")
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_owned());
+        let provider_reference = fields
+            .get("tran_id")
+            .or_else(|| fields.get("val_id"))
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(WebhookEvent {
+            status: webhook_status_label(&status_raw),
+            provider_reference,
+            amount: fields
+                .get("amount")
+                .or_else(|| fields.get("store_amount"))
+                .cloned(),
+            currency: fields.get("currency").cloned(),
+            verified: true,
+            raw: raw.to_string(),
+        })
+    }
+}
+
+/// Concrete backend wrapped by [`PaymentGateway`], one variant per compiled-in provider feature.
+/// Becomes a zero-variant enum (and therefore uninstantiable) if no provider feature is enabled.
+enum PaymentGatewayInner {
+    #[cfg(feature = "shurjopay")]
+    Shurjopay(ShurjopayClient),
+    #[cfg(feature = "portwallet")]
+    Portwallet(PortwalletClient),
+    #[cfg(feature = "aamarpay")]
+    Aamarpay(AamarpayClient),
+    #[cfg(feature = "sslcommerz")]
+    Sslcommerz(SslcommerzClient),
+}
+
+/// Single stable entry point that dispatches to a provider client by name, following eopayment's
+/// dynamic-backend model so callers don't need to import and branch on four distinct classes
+/// themselves, and can make the provider a runtime config value instead of an import-time choice.
+#[pyclass]
+struct PaymentGateway {
+    inner: PaymentGatewayInner,
+}
+
+#[pymethods]
+impl PaymentGateway {
+    /// `provider` must be one of `"shurjopay"`, `"portwallet"`, `"aamarpay"`, `"sslcommerz"` *and*
+    /// be compiled in via the matching Cargo feature; `config` is that provider's usual config
+    /// JSON, unpacked the same way its own `new()` unpacks it.
+    #[new]
+    fn new(provider: &str, config: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let inner = match provider {
+            #[cfg(feature = "shurjopay")]
+            "shurjopay" => PaymentGatewayInner::Shurjopay(ShurjopayClient::new(config)?),
+            #[cfg(feature = "portwallet")]
+            "portwallet" => PaymentGatewayInner::Portwallet(PortwalletClient::new(config)?),
+            #[cfg(feature = "aamarpay")]
+            "aamarpay" => PaymentGatewayInner::Aamarpay(AamarpayClient::new(config)?),
+            #[cfg(feature = "sslcommerz")]
+            "sslcommerz" => PaymentGatewayInner::Sslcommerz(SslcommerzClient::new(config)?),
+            other => {
+                return Err(to_py_err(
+                    BdPaymentError::unsupported(
+                        format!("Unknown or not-compiled-in provider {other:?}."),
+                        "Pass one of: shurjopay, portwallet, aamarpay, sslcommerz, and make sure \
+                         its Cargo feature is enabled in this build.",
+                    ),
+                    Locale::En,
+                ));
+            }
+        };
+        Ok(Self { inner })
+    }
+
+    fn initiate_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<InitiatePaymentResponse> {
+        match &self.inner {
+            #[cfg(feature = "shurjopay")]
+            PaymentGatewayInner::Shurjopay(client) => client.initiate_payment(request),
+            #[cfg(feature = "portwallet")]
+            PaymentGatewayInner::Portwallet(client) => client.initiate_payment(request),
+            #[cfg(feature = "aamarpay")]
+            PaymentGatewayInner::Aamarpay(client) => client.initiate_payment(request),
+            #[cfg(feature = "sslcommerz")]
+            PaymentGatewayInner::Sslcommerz(client) => client.initiate_payment(request),
+        }
+    }
+
+    fn verify_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<VerifyPaymentResponse> {
+        match &self.inner {
+            #[cfg(feature = "shurjopay")]
+            PaymentGatewayInner::Shurjopay(client) => client.verify_payment(request),
+            #[cfg(feature = "portwallet")]
+            PaymentGatewayInner::Portwallet(client) => client.verify_payment(request),
+            #[cfg(feature = "aamarpay")]
+            PaymentGatewayInner::Aamarpay(client) => client.verify_payment(request),
+            #[cfg(feature = "sslcommerz")]
+            PaymentGatewayInner::Sslcommerz(client) => client.verify_payment(request),
+        }
+    }
+
+    /// Forwards to the backend's `refund` for PortWallet and SSLCOMMERZ, and raises `Unsupported`
+    /// for shurjoPay and aamarPay, whose Python client classes expose no `refund` method.
+    fn refund(&self, request: &Bound<'_, PyAny>) -> PyResult<RefundResponse> {
+        match &self.inner {
+            #[cfg(feature = "shurjopay")]
+            PaymentGatewayInner::Shurjopay(client) => Err(to_py_err(
+                BdPaymentError::unsupported(
+                    "shurjoPay refunds are not available through the Python binding.",
+                    "Process this refund out-of-band through merchant support instead.",
+                ),
+                client.locale,
+            )),
+            #[cfg(feature = "portwallet")]
+            PaymentGatewayInner::Portwallet(client) => client.refund(request),
+            #[cfg(feature = "aamarpay")]
+            PaymentGatewayInner::Aamarpay(client) => Err(to_py_err(
+                BdPaymentError::unsupported(
+                    "aamarPay refunds are not available through the Python binding.",
+                    "Process this refund out-of-band through merchant support instead.",
+                ),
+                client.locale,
+            )),
+            #[cfg(feature = "sslcommerz")]
+            PaymentGatewayInner::Sslcommerz(client) => client.refund(request),
+        }
+    }
+}
+
+#[cfg(feature = "mock")]
+#[derive(Deserialize)]
+struct MockConfigInput {
+    /// Maps a merchant-supplied `order_id` to the scripted terminal status (`"paid"`, `"failed"`,
+    /// `"cancelled"`, or `"refunded"`) that `verify_payment` should report for it. Orders absent
+    /// from this map settle to `"paid"` on their own after `settle_after_calls` polls.
+    outcomes: HashMap<String, String>,
+    base_redirect_url: String,
+    /// Number of `verify_payment` calls an unscripted order stays `"pending"` for before settling
+    /// to `"paid"`, emulating an async gateway's callback latency. Defaults to 1.
+    settle_after_calls: Option<u32>,
+}
+
+#[cfg(feature = "mock")]
+#[derive(Deserialize)]
+struct MockOrderRequest {
+    order_id: String,
+}
+
+/// Offline stand-in for a real provider client, modeled on eopayment's `dummy` backend: no
+/// network I/O, a deterministic `provider_reference`/`redirect_url`, and a scripted outcome map so
+/// downstream integration code can be exercised in CI without sandbox credentials.
+#[cfg(feature = "mock")]
+#[pyclass]
+struct MockClient {
+    outcomes: HashMap<String, String>,
+    base_redirect_url: Url,
+    settle_after_calls: u32,
+    call_counts: Mutex<HashMap<String, u32>>,
+}
+
+#[cfg(feature = "mock")]
+#[pymethods]
+impl MockClient {
+    #[new]
+    fn new(config: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let cfg: MockConfigInput = parse_json_input(config, "mock config")?;
+        let base_redirect_url = Url::parse(&cfg.base_redirect_url)
+            .map_err(|e| PyValueError::new_err(format!("Invalid base_redirect_url: {e}")))?;
+        Ok(Self {
+            outcomes: cfg.outcomes,
+            base_redirect_url,
+            settle_after_calls: cfg.settle_after_calls.unwrap_or(1).max(1),
+            call_counts: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn initiate_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<InitiatePaymentResponse> {
+        let request: MockOrderRequest = parse_json_input(request, "mock initiate request")?;
+        let mut redirect_url = self.base_redirect_url.clone();
+        redirect_url
+            .query_pairs_mut()
+            .append_pair("order_id", &request.order_id);
+
+        Ok(InitiatePaymentResponse {
+            redirect_url: redirect_url.to_string(),
+            provider_reference: mock_provider_reference(&request.order_id),
+            raw: json!({ "order_id": request.order_id }).to_string(),
+            request_id: None,
+        })
+    }
+
+    fn verify_payment(&self, request: &Bound<'_, PyAny>) -> PyResult<VerifyPaymentResponse> {
+        let request: MockOrderRequest = parse_json_input(request, "mock verify request")?;
+        let status = self.settled_status(&request.order_id);
+
+        Ok(VerifyPaymentResponse {
+            status,
+            provider_reference: mock_provider_reference(&request.order_id),
+            amount: None,
+            currency: None,
+            raw: json!({ "order_id": request.order_id }).to_string(),
+            request_id: None,
+        })
+    }
+
+    fn refund(&self, request: &Bound<'_, PyAny>) -> PyResult<RefundResponse> {
+        let request: MockOrderRequest = parse_json_input(request, "mock refund request")?;
+        Ok(RefundResponse {
+            status: "completed".to_owned(),
+            provider_reference: mock_provider_reference(&request.order_id),
+            raw: json!({ "order_id": request.order_id }).to_string(),
+            request_id: None,
+        })
+    }
+}
+
+#[cfg(feature = "mock")]
+impl MockClient {
+    /// Looks up the scripted outcome for `order_id`. Scripted orders report their outcome on the
+    /// very first call; unscripted ones stay `"pending"` until `settle_after_calls` calls have
+    /// been made for that order, then settle to `"paid"`.
+    fn settled_status(&self, order_id: &str) -> String {
+        if let Some(status) = self.outcomes.get(order_id) {
+            return status.clone();
+        }
+
+        let mut counts = self
+            .call_counts
+            .lock()
+            .expect("mock call-count mutex poisoned");
+        let count = counts.entry(order_id.to_owned()).or_insert(0);
+        *count += 1;
+        if *count > self.settle_after_calls {
+            "paid".to_owned()
+        } else {
+            "pending".to_owned()
+        }
+    }
+}
+
+#[cfg(feature = "mock")]
+fn mock_provider_reference(order_id: &str) -> String {
+    format!("mock-{order_id}")
 }
 
 #[pymodule]
@@ -553,6 +1864,8 @@ fn _bd_payment_gateway_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<InitiatePaymentResponse>()?;
     m.add_class::<VerifyPaymentResponse>()?;
     m.add_class::<RefundResponse>()?;
+    m.add_class::<WebhookEvent>()?;
+    m.add_class::<PaymentGateway>()?;
 
     #[cfg(feature = "shurjopay")]
     m.add_class::<ShurjopayClient>()?;
@@ -562,13 +1875,18 @@ fn _bd_payment_gateway_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<AamarpayClient>()?;
     #[cfg(feature = "sslcommerz")]
     m.add_class::<SslcommerzClient>()?;
+    #[cfg(feature = "mock")]
+    m.add_class::<MockClient>()?;
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{EnvInput, HttpSettingsInput, parse_environment_raw, parse_http_settings_raw};
+    use super::{
+        EnvInput, HttpSettingsInput, Locale, parse_environment_raw, parse_http_settings_raw,
+        parse_locale_raw,
+    };
 
     #[test]
     fn parse_environment_supports_custom_mode() {
@@ -586,12 +1904,13 @@ mod tests {
 
     #[test]
     fn parse_http_settings_overrides_defaults() {
-        let settings = parse_http_settings_raw(Some(HttpSettingsInput {
+        let (settings, idempotency_ttl) = parse_http_settings_raw(Some(HttpSettingsInput {
             timeout_ms: Some(40_000),
             max_retries: Some(4),
             initial_backoff_ms: Some(250),
             max_backoff_ms: Some(2_000),
             user_agent: Some("bd-payment-gateway-py-test".to_owned()),
+            idempotency_ttl_ms: Some(60_000),
         }))
         .expect("settings should parse");
 
@@ -600,6 +1919,13 @@ mod tests {
         assert_eq!(settings.initial_backoff.as_millis(), 250);
         assert_eq!(settings.max_backoff.as_millis(), 2_000);
         assert_eq!(settings.user_agent, "bd-payment-gateway-py-test");
+        assert_eq!(idempotency_ttl.as_millis(), 60_000);
+    }
+
+    #[test]
+    fn parse_http_settings_defaults_idempotency_ttl_to_24h() {
+        let (_, idempotency_ttl) = parse_http_settings_raw(None).expect("settings should parse");
+        assert_eq!(idempotency_ttl, super::DEFAULT_IDEMPOTENCY_TTL);
     }
 
     #[test]
@@ -610,9 +1936,25 @@ mod tests {
             initial_backoff_ms: Some(1_000),
             max_backoff_ms: Some(100),
             user_agent: None,
+            idempotency_ttl_ms: None,
         }))
         .expect_err("invalid backoff bounds should fail");
 
         assert!(err.contains("initial_backoff_ms"));
     }
+
+    #[test]
+    fn parse_locale_defaults_to_en() {
+        assert!(matches!(
+            parse_locale_raw(None, "test config").expect("default locale should parse"),
+            Locale::En
+        ));
+    }
+
+    #[test]
+    fn parse_locale_rejects_unknown_locale() {
+        let err = parse_locale_raw(Some("tr".to_owned()), "test config")
+            .expect_err("unsupported locale should fail");
+        assert!(err.contains("locale"));
+    }
 }
@@ -1,18 +1,40 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use async_trait::async_trait;
 use bd_payment_gateway_core::{
-    http::add_default_headers, BdPaymentError, Environment, HttpClient, HttpSettings,
-    InitiatePaymentResponse, PaymentProvider, PaymentStatus, RefundResponse, Result,
-    VerifyPaymentResponse,
+    http::add_default_headers, BdPaymentError, Currency, Environment, HttpClient, HttpSettings,
+    InitiatePaymentResponse, Money, PaymentProvider, PaymentStatus, RefundResponse, RefundStatus,
+    Result, VerifyPaymentResponse,
 };
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use rust_decimal::Decimal;
 use secrecy::{ExposeSecret, SecretString};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use tokio::sync::RwLock;
 use url::Url;
 
 const SANDBOX_BASE: &str = "https://sandbox.shurjopayment.com";
 const PRODUCTION_BASE: &str = "https://engine.shurjopayment.com";
 
+/// Treat a cached token as expired this long before its real `expires_at`, so a request that's
+/// in flight right as the token would lapse doesn't get rejected mid-retry.
+const TOKEN_SAFETY_MARGIN: Duration = Duration::from_secs(30);
+
+/// shurjoPay's `/api/get_token` response omits `expires_in` in some sandbox responses; fall back
+/// to a conservative one-hour lifetime (shurjoPay's documented default) rather than treating a
+/// missing field as "never expires".
+const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(3600);
+
+#[derive(Clone)]
+struct CachedToken {
+    token: String,
+    expires_at: Instant,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub username: String,
@@ -51,6 +73,7 @@ pub struct ShurjopayClient {
     config: Config,
     http: HttpClient,
     base_url: Url,
+    token_cache: Arc<RwLock<Option<CachedToken>>>,
 }
 
 impl ShurjopayClient {
@@ -62,10 +85,54 @@ impl ShurjopayClient {
             config,
             http,
             base_url,
+            token_cache: Arc::new(RwLock::new(None)),
         })
     }
 
+    /// Returns a valid auth token, reusing the cached one while it has more than
+    /// [`TOKEN_SAFETY_MARGIN`] left before it expires, otherwise fetching a fresh one.
     async fn fetch_token(&self) -> Result<String> {
+        {
+            let cache = self.token_cache.read().await;
+            if let Some(cached) = cache.as_ref() {
+                if cached.expires_at > Instant::now() + TOKEN_SAFETY_MARGIN {
+                    return Ok(cached.token.clone());
+                }
+            }
+        }
+
+        self.refresh_token().await
+    }
+
+    /// Unconditionally fetches a fresh token from `/api/get_token` and replaces the cache,
+    /// discarding whatever is cached even if it looks unexpired. Useful when a caller already
+    /// knows the cached token was rejected (e.g. a 401 from `/api/secret-pay`).
+    pub async fn force_refresh_token(&self) -> Result<String> {
+        *self.token_cache.write().await = None;
+        self.refresh_token().await
+    }
+
+    async fn refresh_token(&self) -> Result<String> {
+        let mut cache = self.token_cache.write().await;
+
+        // Double-checked locking: another task may have refreshed the token while we were
+        // waiting for the write lock, so avoid a thundering herd of redundant /api/get_token
+        // calls when several requests race on an expired token.
+        if let Some(cached) = cache.as_ref() {
+            if cached.expires_at > Instant::now() + TOKEN_SAFETY_MARGIN {
+                return Ok(cached.token.clone());
+            }
+        }
+
+        let (token, ttl) = self.request_token().await?;
+        *cache = Some(CachedToken {
+            token: token.clone(),
+            expires_at: Instant::now() + ttl,
+        });
+        Ok(token)
+    }
+
+    async fn request_token(&self) -> Result<(String, Duration)> {
         let url = self.base_url.join("/api/get_token").map_err(|e| {
             BdPaymentError::config(
                 format!("Invalid shurjoPay token URL: {e}"),
@@ -80,8 +147,9 @@ impl ShurjopayClient {
 
         let response: TokenResponse = self.http.post_json(&url, HeaderMap::new(), &req).await?;
 
-        if let Some(token) = response.token {
-            if token.trim().is_empty() {
+        let token = match response.token {
+            Some(token) if !token.trim().is_empty() => token,
+            Some(_) => {
                 return Err(BdPaymentError::provider(
                     "shurjoPay returned an empty auth token.",
                     "Verify your shurjoPay username/password and environment (sandbox vs production).",
@@ -89,17 +157,89 @@ impl ShurjopayClient {
                     None,
                 ));
             }
-            return Ok(token);
+            None => {
+                return Err(BdPaymentError::provider(
+                    response
+                        .message
+                        .unwrap_or_else(|| "Unable to get shurjoPay token.".to_owned()),
+                    "Check merchant credentials and confirm IP is allowed by shurjoPay.",
+                    response.sp_code.map(|v| v.to_string()),
+                    None,
+                ));
+            }
+        };
+
+        let ttl = response
+            .expires_in
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TOKEN_TTL);
+
+        Ok((token, ttl))
+    }
+
+    /// Posts `body` to `path` with a bearer token attached, retrying exactly once with a
+    /// force-refreshed token if the gateway rejects the cached one with a 401. shurjoPay tokens
+    /// can be invalidated server-side before their advertised expiry (e.g. a password rotation),
+    /// so this covers that case without making every call pay for a double round trip.
+    async fn post_authorized<T, R>(
+        &self,
+        path: &str,
+        correlation_id: Option<&str>,
+        body: &T,
+    ) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let url = self.base_url.join(path).map_err(|e| {
+            BdPaymentError::config(
+                format!("Invalid shurjoPay URL ({path}): {e}"),
+                "Check environment base URL configuration.",
+            )
+        })?;
+
+        let token = self.fetch_token().await?;
+        let headers = Self::base_headers(&token, correlation_id)?;
+        match self.http.post_json(&url, headers, body).await {
+            Err(BdPaymentError::HttpError {
+                status: Some(401), ..
+            }) => {
+                let token = self.force_refresh_token().await?;
+                let headers = Self::base_headers(&token, correlation_id)?;
+                self.http.post_json(&url, headers, body).await
+            }
+            other => other,
         }
+    }
+
+    /// Parses an inbound IPN POST body or return-redirect query string (decoded into a JSON
+    /// object) and authoritatively confirms the payment by calling `/api/verification`. The
+    /// callback's own status/amount fields are never trusted directly — a redirect's query
+    /// parameters are attacker-controlled, so this always re-verifies server-to-server (via
+    /// [`PaymentProvider::verify_payment`], which normalizes status through [`map_status`]) before
+    /// returning.
+    pub async fn process_callback(
+        &self,
+        raw_query_or_body: &Value,
+    ) -> Result<VerifyPaymentResponse> {
+        let fields = bd_payment_gateway_core::registry::flatten_object(raw_query_or_body);
+        let sp_order_id = fields
+            .get("sp_order_id")
+            .or_else(|| fields.get("order_id"))
+            .cloned()
+            .ok_or_else(|| {
+                BdPaymentError::validation(
+                    "shurjoPay callback is missing sp_order_id/order_id.",
+                    "Confirm the IPN/return URL is posting shurjoPay's documented callback fields.",
+                )
+            })?;
 
-        Err(BdPaymentError::provider(
-            response
-                .message
-                .unwrap_or_else(|| "Unable to get shurjoPay token.".to_owned()),
-            "Check merchant credentials and confirm IP is allowed by shurjoPay.",
-            response.sp_code.map(|v| v.to_string()),
-            None,
-        ))
+        self.verify_payment(&VerifyPaymentRequest {
+            sp_order_id,
+            correlation_id: None,
+        })
+        .await
     }
 
     fn base_headers(token: &str, correlation_id: Option<&str>) -> Result<HeaderMap> {
@@ -165,6 +305,211 @@ impl InitiatePaymentRequest {
         }
         Ok(())
     }
+
+    pub fn builder() -> InitiatePaymentRequestBuilder {
+        InitiatePaymentRequestBuilder::default()
+    }
+}
+
+/// Fluent builder for [`InitiatePaymentRequest`]. Defaults `currency` to `"BDT"` and
+/// `customer_country` to `"Bangladesh"` (shurjoPay's overwhelmingly common case), and `client_ip`
+/// to the loopback address when the caller has none handy (e.g. server-initiated payments); set
+/// it explicitly via [`Self::client_ip`] to forward the real customer IP when available.
+/// `build()` runs the same validation as [`InitiatePaymentRequest::validate`], so a successfully
+/// built request is always send-ready.
+#[derive(Debug, Clone, Default)]
+pub struct InitiatePaymentRequestBuilder {
+    amount: Option<String>,
+    order_id: Option<String>,
+    currency: Option<String>,
+    return_url: Option<Url>,
+    cancel_url: Option<Url>,
+    client_ip: Option<String>,
+    customer_name: Option<String>,
+    customer_phone: Option<String>,
+    customer_email: Option<String>,
+    customer_address: Option<String>,
+    customer_city: Option<String>,
+    customer_state: Option<String>,
+    customer_postcode: Option<String>,
+    customer_country: Option<String>,
+    value1: Option<String>,
+    value2: Option<String>,
+    value3: Option<String>,
+    value4: Option<String>,
+    discount_amount: Option<String>,
+    discount_percent: Option<String>,
+    correlation_id: Option<String>,
+}
+
+impl InitiatePaymentRequestBuilder {
+    pub fn amount(mut self, v: impl Into<String>) -> Self {
+        self.amount = Some(v.into());
+        self
+    }
+
+    pub fn order_id(mut self, v: impl Into<String>) -> Self {
+        self.order_id = Some(v.into());
+        self
+    }
+
+    pub fn currency(mut self, v: impl Into<String>) -> Self {
+        self.currency = Some(v.into());
+        self
+    }
+
+    pub fn return_url(mut self, v: Url) -> Self {
+        self.return_url = Some(v);
+        self
+    }
+
+    pub fn cancel_url(mut self, v: Url) -> Self {
+        self.cancel_url = Some(v);
+        self
+    }
+
+    pub fn client_ip(mut self, v: impl Into<String>) -> Self {
+        self.client_ip = Some(v.into());
+        self
+    }
+
+    pub fn customer_name(mut self, v: impl Into<String>) -> Self {
+        self.customer_name = Some(v.into());
+        self
+    }
+
+    pub fn customer_phone(mut self, v: impl Into<String>) -> Self {
+        self.customer_phone = Some(v.into());
+        self
+    }
+
+    pub fn customer_email(mut self, v: impl Into<String>) -> Self {
+        self.customer_email = Some(v.into());
+        self
+    }
+
+    pub fn customer_address(mut self, v: impl Into<String>) -> Self {
+        self.customer_address = Some(v.into());
+        self
+    }
+
+    pub fn customer_city(mut self, v: impl Into<String>) -> Self {
+        self.customer_city = Some(v.into());
+        self
+    }
+
+    pub fn customer_state(mut self, v: impl Into<String>) -> Self {
+        self.customer_state = Some(v.into());
+        self
+    }
+
+    pub fn customer_postcode(mut self, v: impl Into<String>) -> Self {
+        self.customer_postcode = Some(v.into());
+        self
+    }
+
+    pub fn customer_country(mut self, v: impl Into<String>) -> Self {
+        self.customer_country = Some(v.into());
+        self
+    }
+
+    pub fn value1(mut self, v: impl Into<String>) -> Self {
+        self.value1 = Some(v.into());
+        self
+    }
+
+    pub fn value2(mut self, v: impl Into<String>) -> Self {
+        self.value2 = Some(v.into());
+        self
+    }
+
+    pub fn value3(mut self, v: impl Into<String>) -> Self {
+        self.value3 = Some(v.into());
+        self
+    }
+
+    pub fn value4(mut self, v: impl Into<String>) -> Self {
+        self.value4 = Some(v.into());
+        self
+    }
+
+    pub fn discount_amount(mut self, v: impl Into<String>) -> Self {
+        self.discount_amount = Some(v.into());
+        self
+    }
+
+    pub fn discount_percent(mut self, v: impl Into<String>) -> Self {
+        self.discount_percent = Some(v.into());
+        self
+    }
+
+    pub fn correlation_id(mut self, v: impl Into<String>) -> Self {
+        self.correlation_id = Some(v.into());
+        self
+    }
+
+    fn require(field: Option<String>, message: &str, hint: &str) -> Result<String> {
+        field
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| BdPaymentError::validation(message.to_owned(), hint.to_owned()))
+    }
+
+    pub fn build(self) -> Result<InitiatePaymentRequest> {
+        let req = InitiatePaymentRequest {
+            amount: Self::require(
+                self.amount,
+                "amount is required to build a shurjoPay InitiatePaymentRequest.",
+                "Call .amount(..) before .build().",
+            )?,
+            order_id: Self::require(
+                self.order_id,
+                "order_id is required to build a shurjoPay InitiatePaymentRequest.",
+                "Call .order_id(..) before .build().",
+            )?,
+            currency: self.currency.unwrap_or_else(|| "BDT".to_owned()),
+            return_url: self.return_url.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "return_url is required to build a shurjoPay InitiatePaymentRequest.",
+                    "Call .return_url(..) before .build().",
+                )
+            })?,
+            cancel_url: self.cancel_url.ok_or_else(|| {
+                BdPaymentError::validation(
+                    "cancel_url is required to build a shurjoPay InitiatePaymentRequest.",
+                    "Call .cancel_url(..) before .build().",
+                )
+            })?,
+            client_ip: self.client_ip.unwrap_or_else(|| "127.0.0.1".to_owned()),
+            customer_name: Self::require(
+                self.customer_name,
+                "customer_name is required to build a shurjoPay InitiatePaymentRequest.",
+                "Call .customer_name(..) before .build().",
+            )?,
+            customer_phone: Self::require(
+                self.customer_phone,
+                "customer_phone is required to build a shurjoPay InitiatePaymentRequest.",
+                "Call .customer_phone(..) before .build().",
+            )?,
+            customer_email: self.customer_email.unwrap_or_default(),
+            customer_address: self.customer_address.unwrap_or_default(),
+            customer_city: self.customer_city.unwrap_or_default(),
+            customer_state: self.customer_state.unwrap_or_default(),
+            customer_postcode: self.customer_postcode.unwrap_or_default(),
+            customer_country: self
+                .customer_country
+                .unwrap_or_else(|| "Bangladesh".to_owned()),
+            value1: self.value1,
+            value2: self.value2,
+            value3: self.value3,
+            value4: self.value4,
+            discount_amount: self.discount_amount,
+            discount_percent: self.discount_percent,
+            correlation_id: self.correlation_id,
+        };
+
+        req.validate()?;
+        Ok(req)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -173,6 +518,14 @@ pub struct VerifyPaymentRequest {
     pub correlation_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundRequest {
+    pub sp_order_id: String,
+    pub refund_amount: String,
+    pub reason: Option<String>,
+    pub correlation_id: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct TokenRequest {
     username: String,
@@ -184,6 +537,8 @@ struct TokenResponse {
     token: Option<String>,
     sp_code: Option<i64>,
     message: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -221,7 +576,10 @@ struct SecretPayRequest<'a> {
 impl PaymentProvider for ShurjopayClient {
     type InitiateRequest = InitiatePaymentRequest;
     type VerifyRequest = VerifyPaymentRequest;
-    type RefundRequest = Value;
+    type RefundRequest = RefundRequest;
+    type AuthorizeRequest = ();
+    type CaptureRequest = ();
+    type VoidRequest = ();
 
     async fn initiate_payment(
         &self,
@@ -229,16 +587,6 @@ impl PaymentProvider for ShurjopayClient {
     ) -> Result<InitiatePaymentResponse> {
         req.validate()?;
 
-        let token = self.fetch_token().await?;
-        let headers = Self::base_headers(&token, req.correlation_id.as_deref())?;
-
-        let url = self.base_url.join("/api/secret-pay").map_err(|e| {
-            BdPaymentError::config(
-                format!("Invalid shurjoPay secret-pay URL: {e}"),
-                "Check environment base URL configuration.",
-            )
-        })?;
-
         let body = SecretPayRequest {
             prefix: &self.config.prefix,
             currency: &req.currency,
@@ -263,7 +611,9 @@ impl PaymentProvider for ShurjopayClient {
             value4: req.value4.as_deref(),
         };
 
-        let raw: Value = self.http.post_json(&url, headers, &body).await?;
+        let raw: Value = self
+            .post_authorized("/api/secret-pay", req.correlation_id.as_deref(), &body)
+            .await?;
 
         let redirect_url = raw
             .get("checkout_url")
@@ -297,6 +647,7 @@ impl PaymentProvider for ShurjopayClient {
             provider_reference,
             raw,
             request_id: req.correlation_id.clone(),
+            network_transaction_id: None,
         })
     }
 
@@ -308,67 +659,295 @@ impl PaymentProvider for ShurjopayClient {
             ));
         }
 
-        let token = self.fetch_token().await?;
-        let headers = Self::base_headers(&token, req.correlation_id.as_deref())?;
-
-        let url = self.base_url.join("/api/verification").map_err(|e| {
-            BdPaymentError::config(
-                format!("Invalid shurjoPay verification URL: {e}"),
-                "Check environment base URL configuration.",
-            )
-        })?;
-
         let raw: Value = self
-            .http
-            .post_json(&url, headers, &json!({"order_id": req.sp_order_id}))
+            .post_authorized(
+                "/api/verification",
+                req.correlation_id.as_deref(),
+                &json!({"order_id": req.sp_order_id}),
+            )
             .await?;
 
         let status = map_status(&raw);
+        let money = parse_money(&raw);
+        let failure_reason = failure_reason_for(&status, &raw);
 
         Ok(VerifyPaymentResponse {
             status,
             provider_reference: req.sp_order_id.clone(),
-            amount: None,
-            currency: None,
-            money: None,
+            amount: money.as_ref().map(|m| m.amount),
+            currency: money.as_ref().map(|m| m.currency.clone()),
+            money,
+            raw,
+            request_id: req.correlation_id.clone(),
+            failure_reason,
+            network_transaction_id: None,
+        })
+    }
+
+    async fn refund(&self, req: &Self::RefundRequest) -> Result<RefundResponse> {
+        if req.sp_order_id.trim().is_empty() {
+            return Err(BdPaymentError::validation(
+                "sp_order_id is required for shurjoPay refund.",
+                "Pass the provider reference returned from initiate_payment/verify_payment.",
+            ));
+        }
+        if req.refund_amount.trim().is_empty() {
+            return Err(BdPaymentError::validation(
+                "refund_amount is required for shurjoPay refund.",
+                "Provide a decimal amount as string, e.g. '50.00'.",
+            ));
+        }
+
+        let raw: Value = self
+            .post_authorized(
+                "/api/refund",
+                req.correlation_id.as_deref(),
+                &json!({
+                    "order_id": req.sp_order_id,
+                    "refund_amount": req.refund_amount,
+                    "reason": req.reason,
+                }),
+            )
+            .await?;
+
+        let sp_code = raw.get("sp_code").and_then(Value::as_i64);
+        if matches!(sp_code, Some(code) if code != 1000) {
+            return Err(BdPaymentError::provider(
+                raw.get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("shurjoPay rejected the refund request.")
+                    .to_owned(),
+                "Confirm the order is eligible for refund and the amount does not exceed the paid amount.",
+                sp_code.map(|v| v.to_string()),
+                req.correlation_id.clone(),
+            ));
+        }
+
+        let provider_reference = raw
+            .get("refund_ref_id")
+            .or_else(|| raw.get("sp_order_id"))
+            .and_then(Value::as_str)
+            .unwrap_or(&req.sp_order_id)
+            .to_owned();
+
+        Ok(RefundResponse {
+            status: map_refund_status(&raw),
+            provider_reference,
             raw,
             request_id: req.correlation_id.clone(),
         })
     }
+}
 
-    async fn refund(&self, _req: &Self::RefundRequest) -> Result<RefundResponse> {
-        Err(BdPaymentError::unsupported(
-            "Refund API is not standardized for shurjoPay in this SDK yet.",
-            "Use shurjoPay merchant panel or extend provider crate with your verified refund contract.",
-        ))
+fn map_refund_status(raw: &Value) -> RefundStatus {
+    let status = raw
+        .get("refund_status")
+        .or_else(|| raw.get("status"))
+        .or_else(|| raw.get("message"))
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+        .to_ascii_lowercase();
+
+    if status.contains("complete") || status.contains("success") {
+        RefundStatus::Completed
+    } else if status.contains("pending") || status.contains("process") {
+        RefundStatus::Pending
+    } else if status.contains("fail") {
+        RefundStatus::Failed
+    } else {
+        RefundStatus::Unknown(status)
     }
 }
 
+/// shurjoPay's documented `sp_code` values, authoritative over any free-text status message when
+/// present (unlike `bank_status`/`status`/`message`, the code isn't subject to wording drift
+/// across shurjoPay's partner banks).
+const SP_CODE_STATUS_TABLE: &[(&str, PaymentStatus)] = &[
+    ("1000", PaymentStatus::Paid),
+    ("1001", PaymentStatus::Failed),
+    ("1002", PaymentStatus::Cancelled),
+    ("1003", PaymentStatus::Failed),
+    ("1004", PaymentStatus::Failed),
+    ("1005", PaymentStatus::Pending),
+];
+
+fn map_status_from_code(code: &str) -> Option<PaymentStatus> {
+    SP_CODE_STATUS_TABLE
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, status)| status.clone())
+}
+
+fn value_as_code(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Classifies a shurjoPay verification/IPN payload into a [`PaymentStatus`]. `sp_code` is checked
+/// first against [`SP_CODE_STATUS_TABLE`]; only when it's missing or not in the table does this
+/// fall back to substring-matching `bank_status`/`status`/`message`. An unrecognized code or
+/// message still produces `PaymentStatus::Unknown`, carrying whatever raw value was found so
+/// callers can debug it instead of silently losing it.
 fn map_status(raw: &Value) -> PaymentStatus {
-    let status = if raw.is_array() {
-        raw.get(0)
-            .and_then(|v| v.get("bank_status").or_else(|| v.get("sp_code")))
-            .and_then(Value::as_str)
-            .unwrap_or("unknown")
-    } else {
-        raw.get("bank_status")
-            .or_else(|| raw.get("status"))
-            .or_else(|| raw.get("message"))
-            .and_then(Value::as_str)
-            .unwrap_or("unknown")
+    let obj = payload_object(raw);
+
+    let code = obj.get("sp_code").and_then(value_as_code);
+    if let Some(code) = &code {
+        if let Some(status) = map_status_from_code(code) {
+            return status;
+        }
     }
-    .to_ascii_lowercase();
 
-    if status.contains("success") || status.contains("paid") || status.contains("complete") {
-        PaymentStatus::Paid
-    } else if status.contains("pending") {
-        PaymentStatus::Pending
-    } else if status.contains("cancel") {
-        PaymentStatus::Cancelled
-    } else if status.contains("fail") {
-        PaymentStatus::Failed
+    let text = obj
+        .get("bank_status")
+        .or_else(|| obj.get("status"))
+        .or_else(|| obj.get("message"))
+        .and_then(Value::as_str);
+
+    if let Some(text) = text {
+        let lower = text.to_ascii_lowercase();
+        if lower.contains("success") || lower.contains("paid") || lower.contains("complete") {
+            return PaymentStatus::Paid;
+        } else if lower.contains("pending") {
+            return PaymentStatus::Pending;
+        } else if lower.contains("cancel") {
+            return PaymentStatus::Cancelled;
+        } else if lower.contains("fail") {
+            return PaymentStatus::Failed;
+        }
+    }
+
+    PaymentStatus::Unknown(
+        code.or_else(|| text.map(str::to_owned))
+            .unwrap_or_else(|| "unknown".to_owned()),
+    )
+}
+
+/// Returns shurjoPay's actual result object, unwrapping the single-element array shape the
+/// verification endpoint sometimes wraps its result in (the same shape [`map_status`] already
+/// accounts for).
+fn payload_object(raw: &Value) -> &Value {
+    if raw.is_array() {
+        raw.get(0).unwrap_or(raw)
     } else {
-        PaymentStatus::Unknown(status)
+        raw
+    }
+}
+
+/// Extracts a decline reason out of the same `bank_status`/`message` fields [`map_status`] reads,
+/// for the subset of calls where the status came back `Failed`. `sp_code` (when present) becomes
+/// the reason code, since shurjoPay's documented codes are more stable than its free-text fields.
+fn failure_reason_for(
+    status: &PaymentStatus,
+    raw: &Value,
+) -> Option<bd_payment_gateway_core::FailureReason> {
+    matches!(status, PaymentStatus::Failed).then(|| {
+        let obj = payload_object(raw);
+        let mut reason =
+            bd_payment_gateway_core::extract_failure_reason(obj, "shurjoPay reported a failed payment.");
+        if reason.code.is_none() {
+            reason.code = obj.get("sp_code").and_then(value_as_code);
+        }
+        reason
+    })
+}
+
+fn parse_currency(raw: &str) -> Currency {
+    match raw.to_ascii_uppercase().as_str() {
+        "BDT" => Currency::Bdt,
+        "USD" => Currency::Usd,
+        "EUR" => Currency::Eur,
+        other => Currency::other(other.to_owned()),
+    }
+}
+
+/// Parses shurjoPay's verification amount fields into an exact [`Money`] value. Prefers
+/// `received_amount` (what was actually settled) over `payable_amount`/`amount` (what was
+/// requested), falling back through them in that order since not every response populates all
+/// three. Amounts are parsed with [`Decimal::from_str`] rather than round-tripped through `f64`,
+/// so reconciling against [`Money::to_minor_units`] doesn't drift from float error.
+fn parse_money(raw: &Value) -> Option<Money> {
+    let obj = payload_object(raw);
+    let amount = obj
+        .get("received_amount")
+        .or_else(|| obj.get("payable_amount"))
+        .or_else(|| obj.get("amount"))
+        .and_then(Value::as_str)
+        .and_then(|v| Decimal::from_str(v).ok())?;
+    let currency = obj
+        .get("currency")
+        .and_then(Value::as_str)
+        .map(parse_currency)
+        .unwrap_or(Currency::Bdt);
+
+    Some(Money::new(amount, currency))
+}
+
+/// Minimal JSON shape accepted by this provider's [`bd_payment_gateway_core::registry`]
+/// registration. Deliberately narrower than [`Config`]: it covers the fields every merchant must
+/// supply and leaves `http_settings` at [`HttpSettings::default`], since the registry's `build`
+/// hook has no channel for per-caller tuning knobs.
+#[derive(Deserialize)]
+struct RegistryConfig {
+    username: String,
+    password: String,
+    prefix: String,
+    environment: Environment,
+}
+
+fn registry_build(
+    config: bd_payment_gateway_core::registry::ProviderConfigJson,
+) -> Result<Box<dyn bd_payment_gateway_core::DynPaymentProvider>> {
+    let cfg: RegistryConfig = serde_json::from_value(config).map_err(|e| {
+        BdPaymentError::config(
+            format!("Invalid shurjoPay registry config: {e}"),
+            "Provide username, password, prefix, and environment.",
+        )
+    })?;
+    let client = ShurjopayClient::new(Config {
+        username: cfg.username,
+        password: SecretString::new(cfg.password.into()),
+        prefix: cfg.prefix,
+        environment: cfg.environment,
+        http_settings: HttpSettings::default(),
+    })?;
+    Ok(Box::new(client))
+}
+
+/// shurjoPay publishes no callback signature scheme, so this can only normalize the posted
+/// status/reference fields, not authenticate them; callers still need `verify_payment` to confirm
+/// settlement before acting on the callback.
+fn registry_parse_webhook(
+    payload: &Value,
+) -> Result<bd_payment_gateway_core::registry::NormalizedEvent> {
+    let fields = bd_payment_gateway_core::registry::flatten_object(payload);
+    let status_raw = fields
+        .get("sp_message")
+        .or_else(|| fields.get("status"))
+        .cloned()
+        .unwrap_or_default();
+    let provider_reference = fields
+        .get("sp_order_id")
+        .or_else(|| fields.get("order_id"))
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(bd_payment_gateway_core::registry::NormalizedEvent {
+        provider: "shurjopay",
+        status: PaymentStatus::from_keyword(&status_raw),
+        provider_reference,
+        raw: payload.clone(),
+    })
+}
+
+inventory::submit! {
+    bd_payment_gateway_core::registry::ProviderRegistration {
+        name: "shurjopay",
+        build: registry_build,
+        parse_webhook: registry_parse_webhook,
     }
 }
 
@@ -407,6 +986,81 @@ mod tests {
         assert!(req.validate().is_err());
     }
 
+    #[test]
+    fn builder_applies_defaults_and_validates() {
+        let req = InitiatePaymentRequest::builder()
+            .amount("100.00")
+            .order_id("O-1")
+            .return_url(Url::parse("https://merchant.test/ok").expect("url"))
+            .cancel_url(Url::parse("https://merchant.test/cancel").expect("url"))
+            .customer_name("A")
+            .customer_phone("017")
+            .build()
+            .expect("builds with only the required fields set");
+
+        assert_eq!(req.currency, "BDT");
+        assert_eq!(req.customer_country, "Bangladesh");
+        assert_eq!(req.client_ip, "127.0.0.1");
+    }
+
+    #[test]
+    fn builder_requires_order_id() {
+        let err = InitiatePaymentRequest::builder()
+            .amount("100.00")
+            .return_url(Url::parse("https://merchant.test/ok").expect("url"))
+            .cancel_url(Url::parse("https://merchant.test/cancel").expect("url"))
+            .customer_name("A")
+            .customer_phone("017")
+            .build()
+            .expect_err("order_id is required");
+
+        assert!(matches!(err, BdPaymentError::ValidationError { .. }));
+    }
+
+    #[test]
+    fn map_status_prefers_sp_code_over_text() {
+        // sp_code 1000 is success, but bank_status says "Failed" — the code wins.
+        let raw = serde_json::json!({"sp_code": "1000", "bank_status": "Failed"});
+        assert!(matches!(map_status(&raw), PaymentStatus::Paid));
+    }
+
+    #[test]
+    fn map_status_covers_known_sp_codes() {
+        let cases = [
+            ("1000", PaymentStatus::Paid),
+            ("1001", PaymentStatus::Failed),
+            ("1002", PaymentStatus::Cancelled),
+            ("1003", PaymentStatus::Failed),
+            ("1004", PaymentStatus::Failed),
+            ("1005", PaymentStatus::Pending),
+        ];
+
+        for (code, expected) in cases {
+            let raw = serde_json::json!({"sp_code": code});
+            let status = map_status(&raw);
+            assert_eq!(
+                std::mem::discriminant(&status),
+                std::mem::discriminant(&expected),
+                "sp_code {code} should map to {expected:?}, got {status:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn map_status_falls_back_to_text_when_code_unrecognized() {
+        let raw = serde_json::json!({"sp_code": "9999", "bank_status": "Cancelled by user"});
+        assert!(matches!(map_status(&raw), PaymentStatus::Cancelled));
+    }
+
+    #[test]
+    fn map_status_unknown_preserves_raw_code() {
+        let raw = serde_json::json!({"sp_code": "9999", "bank_status": "something odd"});
+        match map_status(&raw) {
+            PaymentStatus::Unknown(code) => assert_eq!(code, "9999"),
+            other => panic!("expected Unknown, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn initiate_payment_parses_checkout_url() {
         let server = MockServer::start();
@@ -464,4 +1118,145 @@ mod tests {
         assert_eq!(resp.provider_reference, "SP-001");
         assert_eq!(resp.redirect_url.as_str(), "https://checkout.example/123");
     }
+
+    fn test_client(server: &MockServer) -> ShurjopayClient {
+        ShurjopayClient::new(Config {
+            username: "u".to_owned(),
+            password: SecretString::new("p".to_owned().into()),
+            prefix: "PX".to_owned(),
+            environment: Environment::CustomBaseUrl(
+                Url::parse(&server.base_url()).expect("mock server url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client")
+    }
+
+    #[tokio::test]
+    async fn refund_parses_successful_response() {
+        let server = MockServer::start();
+        let _token_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/get_token");
+            then.status(200)
+                .json_body_obj(&serde_json::json!({"token": "tok_123"}));
+        });
+        let _refund_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/refund");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "sp_code": 1000,
+                "refund_ref_id": "RF-001",
+                "refund_status": "completed"
+            }));
+        });
+
+        let client = test_client(&server);
+
+        let resp = client
+            .refund(&RefundRequest {
+                sp_order_id: "SP-001".to_owned(),
+                refund_amount: "50.00".to_owned(),
+                reason: Some("customer request".to_owned()),
+                correlation_id: None,
+            })
+            .await
+            .expect("refund");
+
+        assert_eq!(resp.provider_reference, "RF-001");
+        assert!(matches!(resp.status, RefundStatus::Completed));
+    }
+
+    #[tokio::test]
+    async fn refund_surfaces_provider_rejection() {
+        let server = MockServer::start();
+        let _token_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/get_token");
+            then.status(200)
+                .json_body_obj(&serde_json::json!({"token": "tok_123"}));
+        });
+        let _refund_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/refund");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "sp_code": 1001,
+                "message": "Refund window expired."
+            }));
+        });
+
+        let client = test_client(&server);
+
+        let err = client
+            .refund(&RefundRequest {
+                sp_order_id: "SP-001".to_owned(),
+                refund_amount: "50.00".to_owned(),
+                reason: None,
+                correlation_id: None,
+            })
+            .await
+            .expect_err("refund should be rejected");
+
+        assert!(matches!(err, BdPaymentError::ProviderError { .. }));
+    }
+
+    #[tokio::test]
+    async fn process_callback_reverifies_instead_of_trusting_the_payload() {
+        let server = MockServer::start();
+        let _token_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/get_token");
+            then.status(200)
+                .json_body_obj(&serde_json::json!({"token": "tok_123"}));
+        });
+        let _verify_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/verification");
+            then.status(200)
+                .json_body_obj(&serde_json::json!({"bank_status": "Success"}));
+        });
+
+        let client = test_client(&server);
+
+        // A spoofed callback would claim success directly; process_callback must ignore that and
+        // call /api/verification itself instead of trusting these fields.
+        let callback = serde_json::json!({"sp_order_id": "SP-001", "status": "Success"});
+
+        let resp = client
+            .process_callback(&callback)
+            .await
+            .expect("process_callback");
+
+        assert_eq!(resp.provider_reference, "SP-001");
+        assert!(matches!(resp.status, PaymentStatus::Paid));
+    }
+
+    #[tokio::test]
+    async fn verify_payment_parses_money_from_array_response() {
+        let server = MockServer::start();
+        let _token_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/get_token");
+            then.status(200)
+                .json_body_obj(&serde_json::json!({"token": "tok_123"}));
+        });
+        let _verify_mock = server.mock(|when, then| {
+            when.method(POST).path("/api/verification");
+            then.status(200).json_body_obj(&serde_json::json!([{
+                "bank_status": "Success",
+                "amount": "100.00",
+                "payable_amount": "100.00",
+                "received_amount": "99.50",
+                "currency": "BDT"
+            }]));
+        });
+
+        let client = test_client(&server);
+
+        let resp = client
+            .verify_payment(&VerifyPaymentRequest {
+                sp_order_id: "SP-001".to_owned(),
+                correlation_id: None,
+            })
+            .await
+            .expect("verify");
+
+        let money = resp.money.expect("money should be parsed");
+        assert_eq!(money.amount, Decimal::from_str("99.50").expect("decimal"));
+        assert!(matches!(money.currency, Currency::Bdt));
+        assert_eq!(resp.amount, Some(money.amount));
+    }
 }
@@ -3,8 +3,10 @@ use std::str::FromStr;
 use async_trait::async_trait;
 use bd_payment_gateway_core::{
     BdPaymentError, Currency, Environment, HttpClient, HttpSettings, InitiatePaymentResponse,
-    PaymentProvider, PaymentStatus, RefundResponse, RefundStatus, Result, VerifyPaymentResponse,
+    PaymentProvider, PaymentStatus, PayoutProvider, PayoutResponse, PayoutStatus, RefundResponse,
+    RefundStatus, Result, VerifyPaymentResponse,
 };
+use bd_payment_gateway_core::webhook::verify_sorted_field_md5;
 use reqwest::header::HeaderMap;
 use rust_decimal::Decimal;
 use secrecy::{ExposeSecret, SecretString};
@@ -140,6 +142,86 @@ pub enum RefundRequest {
     },
 }
 
+/// A marketplace/seller disbursement: `beneficiary_account` identifies the bank account or mobile
+/// wallet receiving funds, and `payout_reference` is the merchant's own idempotency reference for
+/// reconciling it against [`PayoutResponse::provider_reference`] later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutRequest {
+    pub beneficiary_account: String,
+    pub amount: String,
+    pub currency: String,
+    pub payout_reference: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyPayoutRequest {
+    pub payout_reference: String,
+}
+
+/// Initiates a tokenized SSLCOMMERZ payment: identical to a normal [`InitiatePaymentRequest`]
+/// except the gateway is asked to store the card against `customer_reference` so a later
+/// [`SslcommerzClient::charge_recurring`] call can reuse it without a hosted checkout redirect.
+/// `customer_reference` must be stable across a customer's lifetime (e.g. an internal user id),
+/// not the one-off `tran_id`, since it's how the provider ties repeat charges back to one card.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiateRecurringRequest {
+    pub payment: InitiatePaymentRequest,
+    pub customer_reference: String,
+}
+
+impl InitiateRecurringRequest {
+    pub fn validate(&self) -> Result<()> {
+        self.payment.validate()?;
+
+        if self.customer_reference.trim().is_empty() {
+            return Err(BdPaymentError::validation(
+                "customer_reference is required to initiate a recurring SSLCOMMERZ payment.",
+                "Pass a stable identifier for this customer so the stored card can be reused.",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// A merchant-initiated charge against a card tokenized by a prior
+/// [`SslcommerzClient::initiate_recurring`] call, identified by the
+/// [`InitiatePaymentResponse::network_transaction_id`] it returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChargeRecurringRequest {
+    pub network_transaction_id: String,
+    pub tran_id: String,
+    pub amount: String,
+    pub currency: String,
+}
+
+impl ChargeRecurringRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.network_transaction_id.trim().is_empty() {
+            return Err(BdPaymentError::validation(
+                "network_transaction_id is required to charge a recurring SSLCOMMERZ payment.",
+                "Pass the token returned from initiate_recurring.",
+            ));
+        }
+
+        if self.tran_id.trim().is_empty() {
+            return Err(BdPaymentError::validation(
+                "tran_id is required for SSLCOMMERZ.",
+                "Use a unique transaction id for this charge.",
+            ));
+        }
+
+        if Decimal::from_str(&self.amount).is_err() {
+            return Err(BdPaymentError::validation(
+                "amount must be numeric for SSLCOMMERZ.",
+                "Use decimal string like '100.00'.",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct InitiateForm<'a> {
     store_id: &'a str,
@@ -172,11 +254,73 @@ struct InitiateForm<'a> {
     value_d: Option<&'a str>,
 }
 
+#[derive(Debug, Serialize)]
+struct PayoutForm<'a> {
+    store_id: &'a str,
+    store_passwd: &'a str,
+    beneficiary_account: &'a str,
+    amount: &'a str,
+    currency: &'a str,
+    payout_reference: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct RecurringForm<'a> {
+    #[serde(flatten)]
+    payment: InitiateForm<'a>,
+    store_card: &'a str,
+    customer_reference: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct ChargeForm<'a> {
+    store_id: &'a str,
+    store_passwd: &'a str,
+    token: &'a str,
+    tran_id: &'a str,
+    amount: &'a str,
+    currency: &'a str,
+}
+
+fn build_initiate_form<'a>(
+    config: &'a Config,
+    req: &'a InitiatePaymentRequest,
+) -> InitiateForm<'a> {
+    InitiateForm {
+        store_id: &config.store_id,
+        store_passwd: config.store_passwd.expose_secret(),
+        total_amount: &req.total_amount,
+        currency: &req.currency,
+        tran_id: &req.tran_id,
+        success_url: req.success_url.as_str(),
+        fail_url: req.fail_url.as_str(),
+        cancel_url: req.cancel_url.as_str(),
+        ipn_url: req.ipn_url.as_ref().map(Url::as_str),
+        shipping_method: req.shipping_method.as_deref().unwrap_or("NO"),
+        product_name: &req.product_name,
+        product_category: &req.product_category,
+        product_profile: &req.product_profile,
+        cus_name: &req.cus_name,
+        cus_email: &req.cus_email,
+        cus_add1: &req.cus_add1,
+        cus_city: &req.cus_city,
+        cus_country: &req.cus_country,
+        cus_phone: &req.cus_phone,
+        value_a: req.value_a.as_deref(),
+        value_b: req.value_b.as_deref(),
+        value_c: req.value_c.as_deref(),
+        value_d: req.value_d.as_deref(),
+    }
+}
+
 #[async_trait]
 impl PaymentProvider for SslcommerzClient {
     type InitiateRequest = InitiatePaymentRequest;
     type VerifyRequest = VerifyPaymentRequest;
     type RefundRequest = RefundRequest;
+    type AuthorizeRequest = ();
+    type CaptureRequest = ();
+    type VoidRequest = ();
 
     async fn initiate_payment(
         &self,
@@ -191,31 +335,7 @@ impl PaymentProvider for SslcommerzClient {
             )
         })?;
 
-        let body = InitiateForm {
-            store_id: &self.config.store_id,
-            store_passwd: self.config.store_passwd.expose_secret(),
-            total_amount: &req.total_amount,
-            currency: &req.currency,
-            tran_id: &req.tran_id,
-            success_url: req.success_url.as_str(),
-            fail_url: req.fail_url.as_str(),
-            cancel_url: req.cancel_url.as_str(),
-            ipn_url: req.ipn_url.as_ref().map(Url::as_str),
-            shipping_method: req.shipping_method.as_deref().unwrap_or("NO"),
-            product_name: &req.product_name,
-            product_category: &req.product_category,
-            product_profile: &req.product_profile,
-            cus_name: &req.cus_name,
-            cus_email: &req.cus_email,
-            cus_add1: &req.cus_add1,
-            cus_city: &req.cus_city,
-            cus_country: &req.cus_country,
-            cus_phone: &req.cus_phone,
-            value_a: req.value_a.as_deref(),
-            value_b: req.value_b.as_deref(),
-            value_c: req.value_c.as_deref(),
-            value_d: req.value_d.as_deref(),
-        };
+        let body = build_initiate_form(&self.config, req);
 
         let raw: Value = self.http.post_form(&url, HeaderMap::new(), &body).await?;
 
@@ -264,6 +384,7 @@ impl PaymentProvider for SslcommerzClient {
             provider_reference,
             raw,
             request_id: None,
+            network_transaction_id: None,
         })
     }
 
@@ -326,6 +447,8 @@ impl PaymentProvider for SslcommerzClient {
             VerifyReference::TranId(v) => v.clone(),
         };
 
+        let failure_reason = failure_reason_for(&payment_status, &raw);
+
         Ok(VerifyPaymentResponse {
             status: payment_status,
             provider_reference,
@@ -336,6 +459,8 @@ impl PaymentProvider for SslcommerzClient {
                 .map(|(amount, currency)| bd_payment_gateway_core::Money { amount, currency }),
             raw,
             request_id: None,
+            failure_reason,
+            network_transaction_id: None,
         })
     }
 
@@ -410,12 +535,342 @@ impl PaymentProvider for SslcommerzClient {
     }
 }
 
+#[async_trait]
+impl PayoutProvider for SslcommerzClient {
+    type PayoutRequest = PayoutRequest;
+    type VerifyPayoutRequest = VerifyPayoutRequest;
+
+    async fn create_payout(&self, req: &Self::PayoutRequest) -> Result<PayoutResponse> {
+        if Decimal::from_str(&req.amount).is_err() {
+            return Err(BdPaymentError::validation(
+                "amount must be numeric for an SSLCOMMERZ payout.",
+                "Use a decimal string like '500.00'.",
+            ));
+        }
+
+        if req.beneficiary_account.trim().is_empty() {
+            return Err(BdPaymentError::validation(
+                "beneficiary_account is required for an SSLCOMMERZ payout.",
+                "Provide the beneficiary's bank account or mobile wallet number.",
+            ));
+        }
+
+        let url = self.base_url.join("/payout/api/v1/disbursement").map_err(|e| {
+            BdPaymentError::config(
+                format!("Invalid SSLCOMMERZ payout URL: {e}"),
+                "Check environment base URL configuration.",
+            )
+        })?;
+
+        let body = PayoutForm {
+            store_id: &self.config.store_id,
+            store_passwd: self.config.store_passwd.expose_secret(),
+            beneficiary_account: &req.beneficiary_account,
+            amount: &req.amount,
+            currency: &req.currency,
+            payout_reference: &req.payout_reference,
+        };
+
+        let raw: Value = self.http.post_json(&url, HeaderMap::new(), &body).await?;
+
+        let status = extract_status(&raw).unwrap_or_else(|| "unknown".to_owned());
+        let provider_reference = raw
+            .get("payout_id")
+            .or_else(|| raw.get("trx_id"))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| req.payout_reference.clone());
+
+        Ok(PayoutResponse {
+            status: map_payout_status(&status),
+            provider_reference,
+            raw,
+            request_id: None,
+        })
+    }
+
+    async fn verify_payout(&self, req: &Self::VerifyPayoutRequest) -> Result<PayoutResponse> {
+        let mut url = self
+            .base_url
+            .join("/payout/api/v1/disbursement/status")
+            .map_err(|e| {
+                BdPaymentError::config(
+                    format!("Invalid SSLCOMMERZ payout status URL: {e}"),
+                    "Check environment base URL configuration.",
+                )
+            })?;
+
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("store_id", &self.config.store_id);
+            qp.append_pair("store_passwd", self.config.store_passwd.expose_secret());
+            qp.append_pair("payout_reference", &req.payout_reference);
+        }
+
+        let raw: Value = self.http.get_json(&url, HeaderMap::new()).await?;
+
+        let status = extract_status(&raw).unwrap_or_else(|| "unknown".to_owned());
+        let provider_reference = raw
+            .get("payout_id")
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| req.payout_reference.clone());
+
+        Ok(PayoutResponse {
+            status: map_payout_status(&status),
+            provider_reference,
+            raw,
+            request_id: None,
+        })
+    }
+}
+
+impl SslcommerzClient {
+    /// Recomputes SSLCOMMERZ's IPN `verify_sign` over the fields named in the payload's
+    /// `verify_key` using the configured `store_passwd`, returning whether it matches the
+    /// signature the gateway sent. Callers must still treat a "true" result as a hint, not a
+    /// substitute for `verify_payment`, since `verify_sign` only proves the payload wasn't
+    /// tampered with in transit, not that it reflects the gateway's latest transaction state.
+    pub fn verify_ipn_signature(&self, fields: &std::collections::HashMap<String, String>) -> bool {
+        let fields: std::collections::BTreeMap<String, String> =
+            fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        verify_sign(&fields, self.config.store_passwd.expose_secret())
+    }
+
+    /// Verifies an inbound SSLCOMMERZ IPN callback's `verify_sign` (see [`verify_sign`]) and, on
+    /// success, maps the posted fields into a [`VerifyPaymentResponse`] via the same
+    /// status/amount extraction [`PaymentProvider::verify_payment`] uses, so a webhook handler
+    /// doesn't also have to round-trip through `validationserverAPI.php` just to act on an IPN.
+    /// On a signature mismatch this returns `BdPaymentError::validation` instead of a response —
+    /// a forged or tampered IPN must never be treated as a confirmed payment.
+    pub fn verify_ipn(
+        &self,
+        params: &std::collections::BTreeMap<String, String>,
+    ) -> Result<VerifyPaymentResponse> {
+        if !verify_sign(params, self.config.store_passwd.expose_secret()) {
+            return Err(BdPaymentError::validation(
+                "SSLCOMMERZ IPN verify_sign did not match the recomputed signature.",
+                "Reject this callback; it may be forged, replayed, or store_passwd is \
+                 misconfigured.",
+            ));
+        }
+
+        let raw = Value::Object(
+            params
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                .collect(),
+        );
+
+        let status = extract_status(&raw).unwrap_or_else(|| "unknown".to_owned());
+        let payment_status = map_payment_status(&status);
+
+        let amount = raw
+            .get("amount")
+            .or_else(|| raw.get("store_amount"))
+            .and_then(|v| {
+                v.as_str()
+                    .and_then(|s| Decimal::from_str(s).ok())
+                    .or_else(|| v.as_f64().and_then(Decimal::from_f64_retain))
+            });
+        let currency = raw
+            .get("currency")
+            .and_then(Value::as_str)
+            .map(parse_currency);
+
+        let provider_reference = params
+            .get("val_id")
+            .or_else(|| params.get("tran_id"))
+            .cloned()
+            .unwrap_or_default();
+
+        let failure_reason = failure_reason_for(&payment_status, &raw);
+
+        Ok(VerifyPaymentResponse {
+            status: payment_status,
+            provider_reference,
+            amount,
+            currency: currency.clone(),
+            money: amount
+                .zip(currency)
+                .map(|(amount, currency)| bd_payment_gateway_core::Money { amount, currency }),
+            raw,
+            request_id: None,
+            failure_reason,
+            network_transaction_id: None,
+        })
+    }
+
+    /// Like [`PaymentProvider::initiate_payment`], but asks SSLCOMMERZ to store the card against
+    /// `req.customer_reference` and surfaces the resulting token as
+    /// [`InitiatePaymentResponse::network_transaction_id`]. Persist that token (and the
+    /// `customer_reference` it was stored under) so a later [`Self::charge_recurring`] call can
+    /// replay it without sending the customer through the hosted checkout again.
+    pub async fn initiate_recurring(
+        &self,
+        req: &InitiateRecurringRequest,
+    ) -> Result<InitiatePaymentResponse> {
+        req.validate()?;
+
+        let url = self.base_url.join("/gwprocess/v4/api.php").map_err(|e| {
+            BdPaymentError::config(
+                format!("Invalid SSLCOMMERZ initiate URL: {e}"),
+                "Check environment base URL configuration.",
+            )
+        })?;
+
+        let body = RecurringForm {
+            payment: build_initiate_form(&self.config, &req.payment),
+            store_card: "true",
+            customer_reference: &req.customer_reference,
+        };
+
+        let raw: Value = self.http.post_form(&url, HeaderMap::new(), &body).await?;
+
+        if is_failure(&raw) {
+            return Err(BdPaymentError::provider(
+                raw.get("failedreason")
+                    .or_else(|| raw.get("status"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("SSLCOMMERZ recurring initiate failed."),
+                "Verify store credentials, return URLs, and transaction fields.",
+                raw.get("error")
+                    .or_else(|| raw.get("status"))
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned),
+                None,
+            ));
+        }
+
+        let redirect_url = raw
+            .get("GatewayPageURL")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                BdPaymentError::parse(
+                    "SSLCOMMERZ response missing GatewayPageURL.",
+                    "Check required request fields and merchant activation state.",
+                )
+            })
+            .and_then(|v| {
+                Url::parse(v).map_err(|e| {
+                    BdPaymentError::parse(
+                        format!("Invalid SSLCOMMERZ GatewayPageURL: {e}"),
+                        "Provider returned malformed URL.",
+                    )
+                })
+            })?;
+
+        let provider_reference = raw
+            .get("sessionkey")
+            .or_else(|| raw.get("tran_id"))
+            .and_then(Value::as_str)
+            .unwrap_or(&req.payment.tran_id)
+            .to_owned();
+
+        let network_transaction_id = raw
+            .get("card_token")
+            .or_else(|| raw.get("token"))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned);
+
+        Ok(InitiatePaymentResponse {
+            redirect_url,
+            provider_reference,
+            raw,
+            request_id: None,
+            network_transaction_id,
+        })
+    }
+
+    /// Charges a card previously tokenized by [`Self::initiate_recurring`], replaying its
+    /// `network_transaction_id` instead of sending the customer through a hosted checkout.
+    /// Returns a [`VerifyPaymentResponse`] (not an [`InitiatePaymentResponse`]) since this call
+    /// settles the charge directly rather than producing a redirect to collect one.
+    pub async fn charge_recurring(
+        &self,
+        req: &ChargeRecurringRequest,
+    ) -> Result<VerifyPaymentResponse> {
+        req.validate()?;
+
+        let url = self
+            .base_url
+            .join("/subscription/api/v1/charge")
+            .map_err(|e| {
+                BdPaymentError::config(
+                    format!("Invalid SSLCOMMERZ recurring charge URL: {e}"),
+                    "Check environment base URL configuration.",
+                )
+            })?;
+
+        let body = ChargeForm {
+            store_id: &self.config.store_id,
+            store_passwd: self.config.store_passwd.expose_secret(),
+            token: &req.network_transaction_id,
+            tran_id: &req.tran_id,
+            amount: &req.amount,
+            currency: &req.currency,
+        };
+
+        let raw: Value = self.http.post_json(&url, HeaderMap::new(), &body).await?;
+
+        let status = extract_status(&raw).unwrap_or_else(|| "unknown".to_owned());
+        let payment_status = map_payment_status(&status);
+
+        let amount = raw
+            .get("amount")
+            .and_then(|v| {
+                v.as_str()
+                    .and_then(|s| Decimal::from_str(s).ok())
+                    .or_else(|| v.as_f64().and_then(Decimal::from_f64_retain))
+            })
+            .or_else(|| Decimal::from_str(&req.amount).ok());
+        let currency = Some(
+            raw.get("currency")
+                .and_then(Value::as_str)
+                .map(parse_currency)
+                .unwrap_or_else(|| parse_currency(&req.currency)),
+        );
+
+        let provider_reference = raw
+            .get("tran_id")
+            .and_then(Value::as_str)
+            .unwrap_or(&req.tran_id)
+            .to_owned();
+
+        let failure_reason = failure_reason_for(&payment_status, &raw);
+
+        Ok(VerifyPaymentResponse {
+            status: payment_status,
+            provider_reference,
+            amount,
+            currency: currency.clone(),
+            money: amount
+                .zip(currency)
+                .map(|(amount, currency)| bd_payment_gateway_core::Money { amount, currency }),
+            raw,
+            request_id: None,
+            failure_reason,
+            network_transaction_id: Some(req.network_transaction_id.clone()),
+        })
+    }
+}
+
+/// Verifies SSLCOMMERZ's IPN hash by delegating to the shared, constant-time-safe
+/// [`verify_sorted_field_md5`], converting the `BTreeMap` this module threads `verify_ipn`/
+/// `verify_ipn_signature` params through into the `HashMap` that helper expects.
+fn verify_sign(fields: &std::collections::BTreeMap<String, String>, store_passwd: &str) -> bool {
+    let fields: std::collections::HashMap<String, String> =
+        fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let secret = SecretString::new(store_passwd.to_owned().into());
+    verify_sorted_field_md5(&fields, "verify_key", "verify_sign", &secret).is_ok()
+}
+
 fn parse_currency(raw: &str) -> Currency {
     match raw.to_ascii_uppercase().as_str() {
         "BDT" => Currency::Bdt,
         "USD" => Currency::Usd,
         "EUR" => Currency::Eur,
-        other => Currency::Other(other.to_owned()),
+        other => Currency::other(other.to_owned()),
     }
 }
 
@@ -433,6 +888,29 @@ fn map_payment_status(status: &str) -> PaymentStatus {
     }
 }
 
+fn map_payout_status(status: &str) -> PayoutStatus {
+    if status.contains("success") || status.contains("complete") || status.contains("done") {
+        PayoutStatus::Completed
+    } else if status.contains("pending") || status.contains("processing") {
+        PayoutStatus::Pending
+    } else if status.contains("fail") || status.contains("invalid") || status.contains("reject") {
+        PayoutStatus::Failed
+    } else {
+        PayoutStatus::Unknown(status.to_owned())
+    }
+}
+
+/// Extracts a decline reason out of whatever reason/message field SSLCommerz's validator
+/// response happens to carry, for the subset of calls where the status came back `Failed`.
+fn failure_reason_for(
+    status: &PaymentStatus,
+    raw: &Value,
+) -> Option<bd_payment_gateway_core::FailureReason> {
+    matches!(status, PaymentStatus::Failed).then(|| {
+        bd_payment_gateway_core::extract_failure_reason(raw, "SSLCommerz reported a failed payment.")
+    })
+}
+
 fn extract_status(raw: &Value) -> Option<String> {
     raw.get("status")
         .or_else(|| raw.get("APIConnect"))
@@ -452,6 +930,68 @@ fn is_failure(raw: &Value) -> bool {
         .unwrap_or(false)
 }
 
+/// Minimal JSON shape accepted by this provider's [`bd_payment_gateway_core::registry`]
+/// registration. Deliberately narrower than [`Config`]: it covers the fields every merchant must
+/// supply and leaves `http_settings` at [`HttpSettings::default`], since the registry's `build`
+/// hook has no channel for per-caller tuning knobs.
+#[derive(Deserialize)]
+struct RegistryConfig {
+    store_id: String,
+    store_passwd: String,
+    environment: Environment,
+}
+
+fn registry_build(
+    config: bd_payment_gateway_core::registry::ProviderConfigJson,
+) -> Result<Box<dyn bd_payment_gateway_core::DynPaymentProvider>> {
+    let cfg: RegistryConfig = serde_json::from_value(config).map_err(|e| {
+        BdPaymentError::config(
+            format!("Invalid SSLCOMMERZ registry config: {e}"),
+            "Provide store_id, store_passwd, and environment.",
+        )
+    })?;
+    let client = SslcommerzClient::new(Config {
+        store_id: cfg.store_id,
+        store_passwd: SecretString::new(cfg.store_passwd.into()),
+        environment: cfg.environment,
+        http_settings: HttpSettings::default(),
+    })?;
+    Ok(Box::new(client))
+}
+
+/// Verifying SSLCOMMERZ's `verify_sign` requires the merchant's `store_passwd`, which this
+/// keyless, registry-level hook has no access to (it only sees the webhook payload, not a live
+/// client instance). This normalizes the posted status/reference fields without authenticating
+/// them; callers still need a configured client's [`SslcommerzClient::verify_ipn`] (or
+/// [`SslcommerzClient::verify_ipn_signature`]) to verify the signature before acting on the
+/// callback.
+fn registry_parse_webhook(
+    payload: &Value,
+) -> Result<bd_payment_gateway_core::registry::NormalizedEvent> {
+    let fields = bd_payment_gateway_core::registry::flatten_object(payload);
+    let status_raw = fields.get("status").cloned().unwrap_or_default();
+    let provider_reference = fields
+        .get("tran_id")
+        .or_else(|| fields.get("val_id"))
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(bd_payment_gateway_core::registry::NormalizedEvent {
+        provider: "sslcommerz",
+        status: PaymentStatus::from_keyword(&status_raw),
+        provider_reference,
+        raw: payload.clone(),
+    })
+}
+
+inventory::submit! {
+    bd_payment_gateway_core::registry::ProviderRegistration {
+        name: "sslcommerz",
+        build: registry_build,
+        parse_webhook: registry_parse_webhook,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -463,6 +1003,106 @@ mod tests {
         assert!(matches!(map_payment_status("valid"), PaymentStatus::Paid));
     }
 
+    /// Builds the `verify_sign` field SSLCOMMERZ would have sent for `fields`, matching
+    /// `verify_sign`'s own "sort the whole `key=value` pairs, including `store_passwd`,
+    /// alphabetically" algorithm, so tests aren't coupled to a specific field ordering.
+    fn expected_verify_sign(
+        fields: &std::collections::BTreeMap<String, String>,
+        store_passwd: &str,
+    ) -> String {
+        let verify_key = fields.get("verify_key").expect("verify_key");
+        let mut pairs: Vec<String> = verify_key
+            .split(',')
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .filter_map(|key| fields.get(key).map(|value| format!("{key}={value}")))
+            .collect();
+        pairs.push(format!("store_passwd={:x}", md5::compute(store_passwd)));
+        pairs.sort_unstable();
+        format!("{:x}", md5::compute(pairs.join("&")))
+    }
+
+    #[test]
+    fn verify_sign_matches_recomputed_signature() {
+        let store_passwd = "pass";
+        let mut fields = std::collections::BTreeMap::new();
+        fields.insert("status".to_owned(), "VALID".to_owned());
+        fields.insert("tran_id".to_owned(), "TXN-1".to_owned());
+        fields.insert("verify_key".to_owned(), "status,tran_id".to_owned());
+
+        let expected = expected_verify_sign(&fields, store_passwd);
+        fields.insert("verify_sign".to_owned(), expected);
+
+        assert!(verify_sign(&fields, store_passwd));
+    }
+
+    #[test]
+    fn verify_sign_rejects_tampered_field() {
+        let store_passwd = "pass";
+        let mut fields = std::collections::BTreeMap::new();
+        fields.insert("status".to_owned(), "VALID".to_owned());
+        fields.insert("tran_id".to_owned(), "TXN-1".to_owned());
+        fields.insert("verify_key".to_owned(), "status,tran_id".to_owned());
+
+        let expected = expected_verify_sign(&fields, store_passwd);
+        fields.insert("verify_sign".to_owned(), expected);
+        fields.insert("tran_id".to_owned(), "TXN-TAMPERED".to_owned());
+
+        assert!(!verify_sign(&fields, store_passwd));
+    }
+
+    #[test]
+    fn verify_ipn_accepts_a_correctly_signed_callback_and_maps_its_status() {
+        let store_passwd = "pass";
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new(store_passwd.to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse("https://sandbox.sslcommerz.com").expect("url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let mut params = std::collections::BTreeMap::new();
+        params.insert("status".to_owned(), "VALID".to_owned());
+        params.insert("tran_id".to_owned(), "TXN-1".to_owned());
+        params.insert("val_id".to_owned(), "VAL-1".to_owned());
+        params.insert("amount".to_owned(), "99.00".to_owned());
+        params.insert("currency".to_owned(), "BDT".to_owned());
+        params.insert(
+            "verify_key".to_owned(),
+            "status,tran_id,val_id,amount,currency".to_owned(),
+        );
+        let expected = expected_verify_sign(&params, store_passwd);
+        params.insert("verify_sign".to_owned(), expected);
+
+        let result = client.verify_ipn(&params).expect("verify_ipn");
+        assert!(matches!(result.status, PaymentStatus::Paid));
+        assert_eq!(result.provider_reference, "VAL-1");
+    }
+
+    #[test]
+    fn verify_ipn_rejects_a_forged_callback() {
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new("pass".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse("https://sandbox.sslcommerz.com").expect("url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let mut params = std::collections::BTreeMap::new();
+        params.insert("status".to_owned(), "VALID".to_owned());
+        params.insert("tran_id".to_owned(), "TXN-1".to_owned());
+        params.insert("verify_key".to_owned(), "status,tran_id".to_owned());
+        params.insert("verify_sign".to_owned(), "deadbeef".to_owned());
+
+        assert!(client.verify_ipn(&params).is_err());
+    }
+
     #[tokio::test]
     async fn initiate_payment_parses_gateway_url() {
         let server = MockServer::start();
@@ -518,4 +1158,206 @@ mod tests {
             "https://sandbox.sslcommerz.com/gw/abc"
         );
     }
+
+    #[tokio::test]
+    async fn create_payout_maps_a_completed_disbursement() {
+        let server = MockServer::start();
+        let _payout_mock = server.mock(|when, then| {
+            when.method(POST).path("/payout/api/v1/disbursement");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "status": "SUCCESS",
+                "payout_id": "PAYOUT-1"
+            }));
+        });
+
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new("pass".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse(&server.base_url()).expect("mock server url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let result = client
+            .create_payout(&PayoutRequest {
+                beneficiary_account: "01700000000".to_owned(),
+                amount: "500.00".to_owned(),
+                currency: "BDT".to_owned(),
+                payout_reference: "PO-1".to_owned(),
+            })
+            .await
+            .expect("create_payout");
+
+        assert!(matches!(result.status, PayoutStatus::Completed));
+        assert_eq!(result.provider_reference, "PAYOUT-1");
+    }
+
+    #[tokio::test]
+    async fn create_payout_rejects_a_non_numeric_amount() {
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new("pass".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse("https://sandbox.sslcommerz.com").expect("url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let result = client
+            .create_payout(&PayoutRequest {
+                beneficiary_account: "01700000000".to_owned(),
+                amount: "not-a-number".to_owned(),
+                currency: "BDT".to_owned(),
+                payout_reference: "PO-1".to_owned(),
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    fn sample_initiate_request() -> InitiatePaymentRequest {
+        InitiatePaymentRequest {
+            total_amount: "99.00".to_owned(),
+            currency: "BDT".to_owned(),
+            tran_id: "TXN-1".to_owned(),
+            success_url: Url::parse("https://merchant.test/s").expect("url"),
+            fail_url: Url::parse("https://merchant.test/f").expect("url"),
+            cancel_url: Url::parse("https://merchant.test/c").expect("url"),
+            ipn_url: None,
+            shipping_method: Some("NO".to_owned()),
+            product_name: "Book".to_owned(),
+            product_category: "General".to_owned(),
+            product_profile: "general".to_owned(),
+            cus_name: "Demo".to_owned(),
+            cus_email: "demo@example.com".to_owned(),
+            cus_add1: "Dhaka".to_owned(),
+            cus_city: "Dhaka".to_owned(),
+            cus_country: "Bangladesh".to_owned(),
+            cus_phone: "017".to_owned(),
+            value_a: None,
+            value_b: None,
+            value_c: None,
+            value_d: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn initiate_recurring_returns_a_network_transaction_id() {
+        let server = MockServer::start();
+        let _init_mock = server.mock(|when, then| {
+            when.method(POST).path("/gwprocess/v4/api.php");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "status": "SUCCESS",
+                "GatewayPageURL": "https://sandbox.sslcommerz.com/gw/abc",
+                "sessionkey": "SSN-1",
+                "card_token": "TOKEN-1"
+            }));
+        });
+
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new("pass".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse(&server.base_url()).expect("mock server url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let result = client
+            .initiate_recurring(&InitiateRecurringRequest {
+                payment: sample_initiate_request(),
+                customer_reference: "CUST-1".to_owned(),
+            })
+            .await
+            .expect("initiate_recurring");
+
+        assert_eq!(result.network_transaction_id.as_deref(), Some("TOKEN-1"));
+    }
+
+    #[tokio::test]
+    async fn initiate_recurring_rejects_a_missing_customer_reference() {
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new("pass".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse("https://sandbox.sslcommerz.com").expect("url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let result = client
+            .initiate_recurring(&InitiateRecurringRequest {
+                payment: sample_initiate_request(),
+                customer_reference: String::new(),
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn charge_recurring_replays_the_stored_token() {
+        let server = MockServer::start();
+        let _charge_mock = server.mock(|when, then| {
+            when.method(POST).path("/subscription/api/v1/charge");
+            then.status(200).json_body_obj(&serde_json::json!({
+                "status": "VALID",
+                "tran_id": "TXN-2",
+                "amount": "50.00",
+                "currency": "BDT"
+            }));
+        });
+
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new("pass".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse(&server.base_url()).expect("mock server url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let result = client
+            .charge_recurring(&ChargeRecurringRequest {
+                network_transaction_id: "TOKEN-1".to_owned(),
+                tran_id: "TXN-2".to_owned(),
+                amount: "50.00".to_owned(),
+                currency: "BDT".to_owned(),
+            })
+            .await
+            .expect("charge_recurring");
+
+        assert!(matches!(result.status, PaymentStatus::Paid));
+        assert_eq!(result.network_transaction_id.as_deref(), Some("TOKEN-1"));
+    }
+
+    #[tokio::test]
+    async fn charge_recurring_rejects_a_missing_token() {
+        let client = SslcommerzClient::new(Config {
+            store_id: "store".to_owned(),
+            store_passwd: SecretString::new("pass".to_owned().into()),
+            environment: Environment::CustomBaseUrl(
+                Url::parse("https://sandbox.sslcommerz.com").expect("url"),
+            ),
+            http_settings: HttpSettings::default(),
+        })
+        .expect("client");
+
+        let result = client
+            .charge_recurring(&ChargeRecurringRequest {
+                network_transaction_id: String::new(),
+                tran_id: "TXN-2".to_owned(),
+                amount: "50.00".to_owned(),
+                currency: "BDT".to_owned(),
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
 }
@@ -12,6 +12,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         signature_key: SecretString::new(std::env::var("AAMARPAY_SIGNATURE_KEY")?.into()),
         environment: Environment::Sandbox,
         http_settings: bd_payment_gateway::core::HttpSettings::default(),
+        refund_endpoint: None,
     })?;
 
     let initiated = client
@@ -32,6 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 zip_code: Some("1207".to_owned()),
                 country: Some("BD".to_owned()),
             },
+            items: Vec::new(),
             correlation_id: None,
         })
         .await?;